@@ -1,6 +1,6 @@
-pub use clap::Parser;
 pub use clap::builder::Styles;
 pub use clap::builder::styling::{AnsiColor, Effects, Style};
+pub use clap::{Args, Parser, Subcommand, ValueEnum};
 
 const STYLES: Styles = Styles::styled()
     .header(AnsiColor::Green.on_default().effects(Effects::BOLD))
@@ -12,7 +12,11 @@ const STYLES: Styles = Styles::styled()
 #[command(name = "action-format", author, version)]
 #[command(about = "A fast GitHub Actions workflow formatter")]
 #[command(styles = STYLES)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Check if files are formatted without modifying them
     #[arg(long, short)]
     pub check: bool,
@@ -21,19 +25,61 @@ pub struct Cli {
     #[arg(long)]
     pub diff: bool,
 
+    /// Verify that formatting is stable and preserves the data of each file
+    #[arg(long)]
+    pub verify: bool,
+
     #[command(flatten)]
     pub global: GlobalArgs,
 }
 
+#[derive(Subcommand)]
+pub enum Command {
+    /// Check files for common mistakes
+    Lint(LintArgs),
+    /// Pin actions to the commit SHAs in .github/actions.lock
+    Pin(PinArgs),
+}
+
+#[derive(Args)]
+pub struct LintArgs {
+    /// Apply the automatic fixes of the problems found
+    #[arg(long)]
+    pub fix: bool,
+
+    /// Describe the schedules of workflows next to the problems found
+    #[arg(long)]
+    pub explain: bool,
+
+    /// Also report the problems found by an installed external tool
+    #[arg(long, value_enum, value_name = "TOOL")]
+    pub external: Vec<ExternalTool>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExternalTool {
+    /// Check `run` scripts with shellcheck
+    Shellcheck,
+    /// Check workflows with actionlint
+    Actionlint,
+}
+
+#[derive(Args)]
+pub struct PinArgs {
+    /// Check that every action is pinned or in the lock file without modifying files
+    #[arg(long, short)]
+    pub check: bool,
+}
+
 #[derive(Parser, Debug, Clone)]
 #[command(next_help_heading = "Global options")]
 pub struct GlobalArgs {
     /// Use quiet output (only show errors)
-    #[arg(long, short)]
+    #[arg(long, short, global = true)]
     pub quiet: bool,
 
     /// Control the use of color in output
-    #[arg(long, value_enum, value_name = "WHEN")]
+    #[arg(long, value_enum, value_name = "WHEN", global = true)]
     pub color: Option<ColorChoice>,
 }
 
@@ -2,16 +2,28 @@ use std::path::Path;
 
 use serde::Deserialize;
 
+use crate::kind::FileKind;
+
 /// Configuration for the formatter.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
 pub struct FormatterConfig {
     /// Number of spaces for indentation (default: 2)
     pub indent_size: usize,
+    /// How block sequence entries are indented relative to their parent key (default: preserve)
+    pub sequence_indent: SequenceIndent,
     /// Whether to add blank lines between steps (default: true)
     pub separate_steps: bool,
     /// Whether to add blank lines between jobs (default: true)
     pub separate_jobs: bool,
+    /// Collections whose entries are separated by blank lines (default: jobs, steps and updates)
+    pub separate: Vec<Collection>,
+    /// Whether to add blank lines between root-level keys (default: false)
+    pub separate_top_level: bool,
+    /// Whether to keep blank lines that aren't added by the formatter (default: true)
+    pub preserve_blank_lines: bool,
+    /// Kinds of files to format (default: workflows and actions)
+    pub include: Vec<FileKind>,
     /// Files to ignore (can be full paths like `.github/workflows/ci.yml` or just filenames like `ci.yml`)
     pub ignore: Vec<String>,
 }
@@ -20,13 +32,87 @@ impl Default for FormatterConfig {
     fn default() -> Self {
         Self {
             indent_size: 2,
+            sequence_indent: SequenceIndent::default(),
             separate_steps: true,
             separate_jobs: true,
+            separate: vec![Collection::Jobs, Collection::Steps, Collection::Updates],
+            separate_top_level: false,
+            preserve_blank_lines: true,
+            include: vec![FileKind::Workflow, FileKind::Action],
             ignore: Vec::new(),
         }
     }
 }
 
+/// Placement of the `-` of block sequence entries nested under a mapping key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SequenceIndent {
+    /// Indent the dash one level deeper than the parent key.
+    Indented,
+    /// Place the dash at the same column as the parent key.
+    Flush,
+    /// Keep whichever placement each sequence uses in the source.
+    #[default]
+    Preserve,
+}
+
+/// A collection of list items or mapping entries that can be separated by blank lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Collection {
+    /// Jobs under `jobs`.
+    Jobs,
+    /// Steps under `jobs.<id>.steps`, and `runs.steps` of composite actions.
+    Steps,
+    /// Matrix entries under `jobs.<id>.strategy.matrix.include`.
+    Include,
+    /// Matrix entries under `jobs.<id>.strategy.matrix.exclude`.
+    Exclude,
+    /// Service containers under `jobs.<id>.services`.
+    Services,
+    /// Inputs of `workflow_dispatch`, `workflow_call` and actions.
+    Inputs,
+    /// Outputs of jobs, `workflow_call` and actions.
+    Outputs,
+    /// Secrets of `workflow_call`.
+    Secrets,
+    /// Dependabot update entries under `updates`.
+    Updates,
+}
+
+impl Collection {
+    /// Key paths from the document root to the collection in files of `kind`, where `*`
+    /// matches any key.
+    pub fn paths(self, kind: FileKind) -> &'static [&'static [&'static str]] {
+        match (kind, self) {
+            (FileKind::Workflow, Self::Jobs) => &[&["jobs"]],
+            (FileKind::Workflow, Self::Steps) => &[&["jobs", "*", "steps"]],
+            (FileKind::Workflow, Self::Include) => {
+                &[&["jobs", "*", "strategy", "matrix", "include"]]
+            }
+            (FileKind::Workflow, Self::Exclude) => {
+                &[&["jobs", "*", "strategy", "matrix", "exclude"]]
+            }
+            (FileKind::Workflow, Self::Services) => &[&["jobs", "*", "services"]],
+            (FileKind::Workflow, Self::Inputs) => &[
+                &["on", "workflow_dispatch", "inputs"],
+                &["on", "workflow_call", "inputs"],
+            ],
+            (FileKind::Workflow, Self::Outputs) => &[
+                &["jobs", "*", "outputs"],
+                &["on", "workflow_call", "outputs"],
+            ],
+            (FileKind::Workflow, Self::Secrets) => &[&["on", "workflow_call", "secrets"]],
+            (FileKind::Action, Self::Steps) => &[&["runs", "steps"]],
+            (FileKind::Action, Self::Inputs) => &[&["inputs"]],
+            (FileKind::Action, Self::Outputs) => &[&["outputs"]],
+            (FileKind::Dependabot, Self::Updates) => &[&["updates"]],
+            _ => &[],
+        }
+    }
+}
+
 impl FormatterConfig {
     /// Load configuration from a TOML file, falling back to defaults if file doesn't exist.
     pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
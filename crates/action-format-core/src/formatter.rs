@@ -1,30 +1,220 @@
 use std::path::Path;
 
-use crate::config::FormatterConfig;
-use crate::parser::FormatError;
+use crate::config::{Collection, FormatterConfig, SequenceIndent};
+use crate::kind::{FileKind, detect_kind};
+use crate::parser::{FormatError, first_difference, parse_documents};
 
-/// Format a YAML string according to the configuration.
+/// Format a workflow according to the configuration.
 pub fn format_string(content: &str, config: &FormatterConfig) -> Result<String, FormatError> {
-    // First pass: detect the source indent size
-    let source_indent = detect_indent_size(content);
+    format_string_with_kind(content, config, FileKind::Workflow)
+}
+
+/// Format a YAML string according to the configuration, applying the rules for `kind`.
+pub fn format_string_with_kind(
+    content: &str,
+    config: &FormatterConfig,
+    kind: FileKind,
+) -> Result<String, FormatError> {
+    let formatted = render(content, config, kind)?;
+
+    // Catch formatter bugs in debug builds; invalid YAML can only be checked for stability
+    if cfg!(debug_assertions) {
+        match verify_format(content, &formatted, config, kind) {
+            Ok(()) | Err(FormatError::Parse { .. }) => {}
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    Ok(formatted)
+}
+
+/// Check that `formatted`, the result of formatting `content`, doesn't change when formatted
+/// again and holds the same data as `content`.
+pub fn verify_format(
+    content: &str,
+    formatted: &str,
+    config: &FormatterConfig,
+    kind: FileKind,
+) -> Result<(), FormatError> {
+    if render(formatted, config, kind)? != formatted {
+        return Err(FormatError::Unstable);
+    }
+
+    let before = parse_documents(content)?;
+    let after = parse_documents(formatted).map_err(|err| match err {
+        FormatError::Parse { line, .. } => FormatError::Changed { line },
+        err => err,
+    })?;
+
+    if before.len() != after.len() {
+        return Err(FormatError::Changed { line: 1 });
+    }
+    if let Some(span) = before
+        .iter()
+        .zip(&after)
+        .find_map(|(before, after)| first_difference(before, after))
+    {
+        return Err(FormatError::Changed { line: span.line });
+    }
+
+    Ok(())
+}
+
+/// Lay out and write the lines of `content`.
+fn render(content: &str, config: &FormatterConfig, kind: FileKind) -> Result<String, FormatError> {
+    let lines = layout(content, config)?;
 
     let mut output = String::with_capacity(content.len());
-    let mut in_steps_section = false;
-    let mut steps_indent: Option<usize> = None;
-    let mut seen_first_step = false;
     let mut first_line = true;
     let mut prev_line_blank = false;
+    let mut prev_line_kept = false;
+
+    for line in &lines {
+        let is_blank = line.kind == LineKind::Blank;
+
+        // Drop blank lines that aren't part of a scalar; required ones are re-added below
+        if is_blank && !config.preserve_blank_lines {
+            continue;
+        }
+
+        // Add blank line before entries of separated collections (except the first one, and only if not already blank)
+        if let Some(entry) = &line.entry {
+            if entry.index > 0
+                && !prev_line_blank
+                && !prev_line_kept
+                && is_separated(config, kind, &entry.path)
+            {
+                output.push('\n');
+            }
+        }
+
+        if !first_line {
+            output.push('\n');
+        }
+        first_line = false;
+
+        // Write the line with its new indentation (skip indent for blank lines)
+        if !is_blank {
+            for _ in 0..line.indent {
+                output.push(' ');
+            }
+        }
+        output.push_str(&line.text);
+
+        prev_line_blank = line.text.is_empty();
+        prev_line_kept = line.kind == LineKind::Kept;
+    }
+
+    // Preserve trailing newline if present
+    if content.ends_with('\n') {
+        output.push('\n');
+    }
+
+    Ok(output)
+}
 
-    // Job tracking
-    let mut in_jobs_section = false;
-    let mut jobs_indent: Option<usize> = None;
-    let mut seen_first_job = false;
+/// Whether entries of the collection at `path` should be separated by blank lines.
+fn is_separated(config: &FormatterConfig, kind: FileKind, path: &[String]) -> bool {
+    if path.is_empty() {
+        return config.separate_top_level;
+    }
+
+    config.separate.iter().any(|collection| match collection {
+        Collection::Steps if !config.separate_steps => false,
+        Collection::Jobs if !config.separate_jobs => false,
+        collection => collection
+            .paths(kind)
+            .iter()
+            .any(|pattern| path_matches(pattern, path)),
+    })
+}
+
+/// Whether a key path matches a pattern, where `*` matches any single key.
+fn path_matches(pattern: &[&str], path: &[String]) -> bool {
+    pattern.len() == path.len()
+        && pattern
+            .iter()
+            .zip(path)
+            .all(|(expected, key)| *expected == "*" || expected == key)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineKind {
+    Blank,
+    Comment,
+    Content,
+    /// A line inside a literal (`|`) or folded (`>`) block scalar.
+    Scalar,
+    /// A line inside a block scalar that keeps its trailing blank lines (`|+`).
+    Kept,
+}
+
+/// A source line together with the indentation it will be written at.
+#[derive(Debug)]
+struct Line {
+    kind: LineKind,
+    /// Indentation of the line in the source, with tabs counted as two spaces.
+    column: usize,
+    /// Indentation of the line in the output.
+    indent: usize,
+    /// The line without its leading indentation.
+    text: String,
+    /// The collection entry this line starts, if any.
+    entry: Option<Entry>,
+}
+
+/// The start of a mapping key or sequence item.
+#[derive(Debug)]
+struct Entry {
+    /// Keys leading from the document root to the collection (`-` for sequence items).
+    path: Vec<String>,
+    /// Position of the entry within its collection.
+    index: usize,
+}
+
+/// A block node that is still open, keyed by the column it starts at in the source.
+#[derive(Debug, Clone)]
+struct Frame {
+    column: usize,
+    indent: usize,
+    sequence: bool,
+    path: Vec<String>,
+    /// Key of the latest entry, if the node is a mapping.
+    key: Option<String>,
+    entries: usize,
+}
+
+/// The node owning a block scalar whose content lines are being read.
+#[derive(Debug, Clone, Copy)]
+struct BlockScalar {
+    column: usize,
+    indent: usize,
+    /// Column of the first content line, used to keep relative indentation.
+    base: Option<usize>,
+    /// Whether the header has an explicit indentation indicator (e.g. `|2`).
+    explicit: bool,
+    /// Whether trailing blank lines belong to the scalar (`|+`).
+    keep: bool,
+}
+
+/// A plain or quoted scalar that may continue on the following lines.
+#[derive(Debug, Clone, Copy)]
+enum FlowScalar {
+    /// A plain scalar, continued by lines indented more than the node at `column`.
+    Plain { column: usize },
+    /// A quoted scalar whose closing quote hasn't been read yet.
+    Quoted(char),
+}
+
+/// Compute the output indentation of every line from the block structure of the document.
+fn layout(content: &str, config: &FormatterConfig) -> Result<Vec<Line>, FormatError> {
+    let mut indenter = Indenter::new(config);
+    let mut lines = Vec::new();
+    let mut block_scalar: Option<BlockScalar> = None;
+    let mut flow_scalar: Option<FlowScalar> = None;
 
     for (line_num, line) in content.lines().enumerate() {
         let trimmed = line.trim_start();
-        let is_blank = trimmed.is_empty();
-
-        // Calculate original indentation
         let original_indent = line.len() - trimmed.len();
 
         // Check for mixed tabs and spaces
@@ -34,7 +224,7 @@ pub fn format_string(content: &str, config: &FormatterConfig) -> Result<String,
         }
 
         // Convert tabs to spaces for calculation
-        let indent_spaces = if indent_chars.contains('\t') {
+        let column = if indent_chars.contains('\t') {
             indent_chars
                 .chars()
                 .map(|c| if c == '\t' { 2 } else { 1 })
@@ -43,166 +233,506 @@ pub fn format_string(content: &str, config: &FormatterConfig) -> Result<String,
             original_indent
         };
 
-        // Detect jobs: key at root level (indent 0)
-        if indent_spaces == 0 && trimmed.starts_with("jobs:") {
-            in_jobs_section = true;
-            jobs_indent = None;
-            seen_first_job = false;
-        }
-
-        // Check if we've exited the jobs section (another root-level key)
-        if in_jobs_section && indent_spaces == 0 && !is_blank && !trimmed.starts_with('#') {
-            if !trimmed.starts_with("jobs:") && trimmed.contains(':') {
-                in_jobs_section = false;
-                jobs_indent = None;
-                seen_first_job = false;
-            }
-        }
-
-        // Detect if we're at a job definition (key: at jobs level)
-        let is_job_item = if in_jobs_section
-            && !is_blank
-            && !trimmed.starts_with('#')
-            && !trimmed.starts_with('-')
-            && trimmed.contains(':')
-        {
-            match jobs_indent {
-                None => {
-                    if indent_spaces > 0 {
-                        jobs_indent = Some(indent_spaces);
-                        true
-                    } else {
-                        false
+        if trimmed.is_empty() {
+            let kept = block_scalar.is_some_and(|scalar| scalar.keep);
+            let quoted = matches!(flow_scalar, Some(FlowScalar::Quoted(_)));
+            lines.push(Line {
+                kind: if kept {
+                    LineKind::Kept
+                } else if quoted {
+                    LineKind::Scalar
+                } else {
+                    LineKind::Blank
+                },
+                column,
+                indent: 0,
+                text: String::new(),
+                entry: None,
+            });
+            continue;
+        }
+
+        if let Some(scalar) = block_scalar.as_mut() {
+            if column > scalar.column {
+                let base = *scalar.base.get_or_insert(column);
+                let indent = if scalar.explicit || column < base {
+                    (column + scalar.indent).saturating_sub(scalar.column)
+                } else {
+                    scalar.indent + config.indent_size + column - base
+                };
+                // Blank lines followed by more content are part of the scalar
+                for blank in lines.iter_mut().rev() {
+                    if blank.kind != LineKind::Blank {
+                        break;
                     }
+                    blank.kind = LineKind::Scalar;
                 }
-                Some(expected) => indent_spaces == expected,
+                lines.push(Line {
+                    kind: if scalar.keep {
+                        LineKind::Kept
+                    } else {
+                        LineKind::Scalar
+                    },
+                    column,
+                    indent,
+                    text: trimmed.to_string(),
+                    entry: None,
+                });
+                continue;
             }
-        } else {
-            false
-        };
-
-        // Detect steps: key at any level
-        if trimmed.starts_with("steps:") {
-            in_steps_section = true;
-            steps_indent = None;
-            seen_first_step = false;
+            block_scalar = None;
         }
 
-        // Detect if we're a step item (- at the steps level)
-        let is_step_item = if in_steps_section && trimmed.starts_with("- ") {
-            match steps_indent {
-                None => {
-                    steps_indent = Some(indent_spaces);
-                    true
+        // Lines that continue a plain or quoted scalar, and the blank lines before them, are part
+        // of its value
+        let continued = match flow_scalar {
+            Some(FlowScalar::Quoted(quote)) => {
+                if is_closed(trimmed, quote) {
+                    flow_scalar = None;
                 }
-                Some(expected) => indent_spaces == expected,
+                true
+            }
+            Some(FlowScalar::Plain { column: owner })
+                if column > owner && !trimmed.starts_with('#') =>
+            {
+                for blank in lines.iter_mut().rev() {
+                    if blank.kind != LineKind::Blank {
+                        break;
+                    }
+                    blank.kind = LineKind::Scalar;
+                }
+                true
+            }
+            _ => {
+                flow_scalar = None;
+                false
             }
-        } else {
-            false
         };
 
-        // Check if we've exited the steps section
-        if in_steps_section && !is_blank && !trimmed.starts_with('#') {
-            if let Some(si) = steps_indent {
-                if indent_spaces < si && !is_step_item {
-                    if trimmed.contains(':') && !trimmed.starts_with('-') {
-                        in_steps_section = false;
-                        steps_indent = None;
-                        seen_first_step = false;
-                    }
+        // Document markers end everything open in the previous document, and each document starts
+        // its own top-level collection
+        if !continued && is_document_marker(line) {
+            indenter = Indenter::new(config);
+            block_scalar = None;
+            flow_scalar = None;
+            lines.push(Line {
+                kind: LineKind::Content,
+                column,
+                indent: 0,
+                text: trimmed.to_string(),
+                entry: None,
+            });
+            continue;
+        }
+
+        if trimmed.starts_with('#') {
+            lines.push(Line {
+                kind: LineKind::Comment,
+                column,
+                indent: indenter.peek(column),
+                text: trimmed.to_string(),
+                entry: None,
+            });
+            continue;
+        }
+
+        let placed = indenter.place(column, trimmed);
+        block_scalar = placed.block_scalar;
+        if !continued && block_scalar.is_none() {
+            flow_scalar = multi_line_scalar(trimmed, column);
+        }
+        lines.push(Line {
+            kind: LineKind::Content,
+            column,
+            indent: placed.indent,
+            text: placed.text,
+            entry: Some(placed.entry),
+        });
+    }
+
+    align_comments(&mut lines);
+
+    Ok(lines)
+}
+
+/// Whether `line` is a `---` or `...` document marker, optionally followed by a comment.
+fn is_document_marker(line: &str) -> bool {
+    let Some(rest) = line
+        .strip_prefix("---")
+        .or_else(|| line.strip_prefix("..."))
+    else {
+        return false;
+    };
+    let comment = rest.trim_start();
+    comment.is_empty() || (comment.starts_with('#') && comment.len() < rest.len())
+}
+
+/// Indent comment lines like the content they sit next to when they share its source column.
+fn align_comments(lines: &mut [Line]) {
+    let mut previous: Option<(usize, usize)> = None;
+
+    for index in 0..lines.len() {
+        match lines[index].kind {
+            LineKind::Content => previous = Some((lines[index].column, lines[index].indent)),
+            LineKind::Comment => {
+                let column = lines[index].column;
+                let next = lines[index + 1..]
+                    .iter()
+                    .find(|line| line.kind == LineKind::Content)
+                    .map(|line| (line.column, line.indent));
+
+                if let Some((_, indent)) = next.filter(|(c, _)| *c == column) {
+                    lines[index].indent = indent;
+                } else if let Some((_, indent)) = previous.filter(|(c, _)| *c == column) {
+                    lines[index].indent = indent;
                 }
             }
+            LineKind::Blank | LineKind::Scalar | LineKind::Kept => {}
         }
+    }
+}
 
-        // Add blank line before job items (except the first one, and only if not already blank)
-        if config.separate_jobs && is_job_item && seen_first_job && !prev_line_blank {
-            output.push('\n');
+/// The result of placing a content line.
+struct Placed {
+    indent: usize,
+    text: String,
+    entry: Entry,
+    block_scalar: Option<BlockScalar>,
+}
+
+/// Tracks the open block nodes to map source columns onto output indentation.
+struct Indenter {
+    frames: Vec<Frame>,
+    indent_size: usize,
+    sequence_indent: SequenceIndent,
+}
+
+impl Indenter {
+    fn new(config: &FormatterConfig) -> Self {
+        Self {
+            frames: Vec::new(),
+            indent_size: config.indent_size,
+            sequence_indent: config.sequence_indent,
         }
+    }
 
-        // Update seen_first_job after we've used it for the blank line decision
-        if is_job_item {
-            seen_first_job = true;
+    /// Output indentation for a line at `column` that doesn't affect the structure.
+    fn peek(&self, column: usize) -> usize {
+        match self.frames.iter().rev().find(|f| f.column <= column) {
+            Some(frame) if frame.column == column => frame.indent,
+            Some(frame) => self.child_indent(frame),
+            None => 0,
         }
+    }
 
-        // Add blank line before step items (except the first one, and only if not already blank)
-        if config.separate_steps && is_step_item && seen_first_step && !prev_line_blank {
-            output.push('\n');
+    fn child_indent(&self, parent: &Frame) -> usize {
+        if parent.sequence {
+            parent.indent + 2
+        } else {
+            parent.indent + self.indent_size
         }
+    }
 
-        // Update seen_first_step after we've used it for the blank line decision
-        if is_step_item {
-            seen_first_step = true;
+    /// Indentation of a block sequence whose parent is a mapping key.
+    fn sequence_indent(&self, parent: &Frame, flush_in_source: bool) -> usize {
+        let flush = match self.sequence_indent {
+            SequenceIndent::Indented => false,
+            SequenceIndent::Flush => true,
+            SequenceIndent::Preserve => flush_in_source,
+        };
+        if flush {
+            parent.indent
+        } else {
+            parent.indent + self.indent_size
         }
+    }
 
-        // Normalize indentation
-        let normalized_indent = normalize_indent(indent_spaces, source_indent, config.indent_size);
+    fn place(&mut self, column: usize, text: &str) -> Placed {
+        let is_entry = sequence_entry(text).is_some();
 
-        if !first_line {
-            output.push('\n');
+        // Close nodes that are deeper than this line, and a sequence that is
+        // followed by a key at the same column (`key:\n- a\nnext:`).
+        while let Some(top) = self.frames.last() {
+            if top.column > column || (top.column == column && top.sequence && !is_entry) {
+                self.frames.pop();
+            } else {
+                break;
+            }
         }
-        first_line = false;
 
-        // Write the line with normalized indentation (skip indent for blank lines)
-        if !is_blank {
-            for _ in 0..normalized_indent {
-                output.push(' ');
+        let indent = match self.frames.last() {
+            None => {
+                self.open(column, 0, is_entry);
+                0
             }
+            Some(top) if top.column == column && (top.sequence || !is_entry) => top.indent,
+            Some(top) => {
+                let indent = if is_entry && !top.sequence {
+                    self.sequence_indent(top, top.column == column)
+                } else {
+                    self.child_indent(top)
+                };
+                self.open(column, indent, is_entry);
+                indent
+            }
+        };
+        let entry = self.enter(text);
+
+        // Open a node for everything that follows a `- ` on the same line.
+        let mut normalized = String::with_capacity(text.len());
+        let mut rest = text;
+        let mut node = (column, indent);
+        let mut owner = node;
+        while let Some(after) = sequence_entry(rest) {
+            normalized.push('-');
+            let value = after.trim_start();
+            owner = node;
+            if value.is_empty() || value.starts_with('#') {
+                if !value.is_empty() {
+                    normalized.push(' ');
+                }
+                rest = value;
+                break;
+            }
+            normalized.push(' ');
+            node = (node.0 + 1 + after.len() - value.len(), node.1 + 2);
+            rest = value;
+            self.open(node.0, node.1, sequence_entry(rest).is_some());
+            self.enter(rest);
         }
-        output.push_str(trimmed);
+        normalized.push_str(rest);
+
+        let block_scalar = block_scalar_header(rest).map(|header| {
+            let (column, indent) = if header.keyed || owner == node {
+                node
+            } else {
+                owner
+            };
+            BlockScalar {
+                column,
+                indent,
+                base: None,
+                explicit: header.explicit,
+                keep: header.keep,
+            }
+        });
 
-        prev_line_blank = is_blank;
+        Placed {
+            indent,
+            text: normalized,
+            entry,
+            block_scalar,
+        }
     }
 
-    // Preserve trailing newline if present
-    if content.ends_with('\n') {
-        output.push('\n');
+    /// Open a node below the current one.
+    fn open(&mut self, column: usize, indent: usize, sequence: bool) {
+        let path = match self.frames.last() {
+            Some(parent) => {
+                let mut path = parent.path.clone();
+                path.push(if parent.sequence {
+                    "-".to_string()
+                } else {
+                    parent.key.clone().unwrap_or_default()
+                });
+                path
+            }
+            None => Vec::new(),
+        };
+        self.frames.push(Frame {
+            column,
+            indent,
+            sequence,
+            path,
+            key: None,
+            entries: 0,
+        });
     }
 
-    Ok(output)
+    /// Record a new entry of the current node starting with `text`.
+    fn enter(&mut self, text: &str) -> Entry {
+        let frame = self
+            .frames
+            .last_mut()
+            .expect("a node is open for every content line");
+        if !frame.sequence {
+            frame.key = mapping_key(text).map(str::to_string);
+        }
+        frame.entries += 1;
+        Entry {
+            path: frame.path.clone(),
+            index: frame.entries - 1,
+        }
+    }
 }
 
-/// Detect the indent size used in the source file.
-fn detect_indent_size(content: &str) -> usize {
-    let mut min_indent: Option<usize> = None;
+/// If `text` is a block sequence entry, return what follows the `-`.
+fn sequence_entry(text: &str) -> Option<&str> {
+    let after = text.strip_prefix('-')?;
+    (after.is_empty() || after.starts_with([' ', '\t'])).then_some(after)
+}
 
-    for line in content.lines() {
-        let trimmed = line.trim_start();
-        if trimmed.is_empty() {
-            continue;
-        }
+/// The indicators of a block scalar header.
+struct Header {
+    /// Whether the scalar is the value of a mapping key.
+    keyed: bool,
+    explicit: bool,
+    keep: bool,
+}
 
-        let indent = line.len() - trimmed.len();
-        if indent > 0 {
-            min_indent = Some(match min_indent {
-                None => indent,
-                Some(current) => gcd(current, indent),
-            });
+/// The key of a `key: value` mapping entry.
+pub(crate) fn mapping_key(text: &str) -> Option<&str> {
+    let text = strip_comment(text).trim_end();
+
+    if let Some(quote) = text.chars().next().filter(|c| matches!(c, '"' | '\'')) {
+        let end = text[1..].find(quote)? + 1;
+        let after = text[end + 1..].trim_start();
+        return after.starts_with(':').then(|| &text[1..end]);
+    }
+
+    if text.starts_with(['{', '[', '?']) {
+        return None;
+    }
+
+    text.char_indices().find_map(|(index, c)| {
+        let after = &text[index + 1..];
+        (c == ':' && (after.is_empty() || after.starts_with([' ', '\t'])))
+            .then(|| text[..index].trim_end())
+    })
+}
+
+/// The scalar value of a content line that can continue on the next lines: a plain scalar, or a
+/// quoted scalar without its closing quote.
+fn multi_line_scalar(text: &str, column: usize) -> Option<FlowScalar> {
+    let mut rest = text;
+    let mut column = column;
+    let mut owner = column;
+    while let Some(after) = sequence_entry(rest) {
+        let value = after.trim_start();
+        owner = column;
+        column += 1 + after.len() - value.len();
+        rest = value;
+    }
+
+    if let Some(key) = mapping_key(rest) {
+        owner = column;
+        let key_end = key.as_ptr() as usize - rest.as_ptr() as usize + key.len();
+        let colon = rest[key_end..].find(':')? + key_end;
+        rest = &rest[colon + 1..];
+    }
+
+    // Skip anchors and tags
+    let mut value = strip_comment(rest).trim();
+    while value.starts_with(['&', '!']) {
+        value = value
+            .split_once([' ', '\t'])
+            .map_or("", |(_, rest)| rest.trim_start());
+    }
+
+    match value.chars().next()? {
+        '|' | '>' | '[' | '{' | '*' => None,
+        quote @ ('"' | '\'') => {
+            (!is_closed(&value[1..], quote)).then_some(FlowScalar::Quoted(quote))
         }
+        _ => Some(FlowScalar::Plain { column: owner }),
     }
+}
 
-    min_indent.unwrap_or(2)
+/// Whether `text`, which starts inside a scalar quoted with `quote`, contains the closing quote.
+fn is_closed(text: &str, quote: char) -> bool {
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if quote == '"' => {
+                chars.next();
+            }
+            '\'' if quote == '\'' => {
+                if !chars.as_str().starts_with('\'') {
+                    return true;
+                }
+                chars.next();
+            }
+            '"' if quote == '"' => return true,
+            _ => {}
+        }
+    }
+    false
 }
 
-/// Greatest common divisor.
-fn gcd(a: usize, b: usize) -> usize {
-    if b == 0 { a } else { gcd(b, a % b) }
+/// Detect a block scalar header (`key: |`, `- >-`, `key: &anchor |2`).
+fn block_scalar_header(text: &str) -> Option<Header> {
+    let value = strip_comment(text).trim_end();
+    let (before, indicator) = match value.rsplit_once(' ') {
+        Some((before, indicator)) => (before.trim_end(), indicator),
+        None => ("", value),
+    };
+
+    let modifiers = indicator.strip_prefix(['|', '>'])?;
+    if modifiers.len() > 2
+        || !modifiers
+            .chars()
+            .all(|c| matches!(c, '+' | '-' | '1'..='9'))
+    {
+        return None;
+    }
+    let explicit = modifiers.chars().any(|c| c.is_ascii_digit());
+    let keep = modifiers.contains('+');
+
+    // Skip over any anchor or tag properties preceding the indicator
+    let mut before = before;
+    while let Some((head, last)) = before.rsplit_once(' ') {
+        if last.starts_with(['&', '!']) {
+            before = head.trim_end();
+        } else {
+            break;
+        }
+    }
+    if before.starts_with(['&', '!']) && !before.contains(' ') {
+        before = "";
+    }
+
+    if !before.is_empty() && !before.ends_with(':') {
+        return None;
+    }
+
+    Some(Header {
+        keyed: !before.is_empty(),
+        explicit,
+        keep,
+    })
 }
 
-/// Normalize indentation from source indent size to target indent size.
-fn normalize_indent(current: usize, source_size: usize, target_size: usize) -> usize {
-    if current == 0 || source_size == 0 {
-        return 0;
+/// Remove a trailing `# comment` that isn't inside a quoted scalar.
+fn strip_comment(text: &str) -> &str {
+    let mut quote: Option<char> = None;
+    let mut prev = ' ';
+
+    for (index, c) in text.char_indices() {
+        match quote {
+            Some('"') if c == '\\' && prev == '\\' => {
+                prev = ' ';
+                continue;
+            }
+            Some(q) if c == q && !(q == '"' && prev == '\\') => quote = None,
+            Some(_) => {}
+            None if (c == '"' || c == '\'')
+                && matches!(prev, ' ' | ':' | '-' | '[' | '{' | ',') =>
+            {
+                quote = Some(c);
+            }
+            None if c == '#' && prev.is_whitespace() => return &text[..index],
+            None => {}
+        }
+        prev = c;
     }
 
-    // Calculate the indent level based on source size, then apply target size
-    let level = current / source_size;
-    level * target_size
+    text
 }
 
-/// Format a YAML file in place.
+/// Format a YAML file in place, applying the rules for the kind detected by [`detect_kind`].
 pub fn format_file(path: &Path, config: &FormatterConfig) -> Result<bool, FormatError> {
     let content = std::fs::read_to_string(path)?;
-    let formatted = format_string(&content, config)?;
+    let formatted = format_string_with_kind(&content, config, detect_kind(path, &content))?;
 
     if content == formatted {
         return Ok(false);
@@ -0,0 +1,86 @@
+use std::path::{Component, Path};
+
+use serde::Deserialize;
+
+use crate::formatter::mapping_key;
+
+/// The kind of YAML file, which decides where it is found and how it is formatted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FileKind {
+    /// A workflow directly in `.github/workflows`.
+    Workflow,
+    /// Action metadata in an `action.yml` or `action.yaml` file.
+    Action,
+    /// Dependabot configuration in `.github/dependabot.yml`.
+    Dependabot,
+    /// An issue form in `.github/ISSUE_TEMPLATE`.
+    IssueForm,
+    /// Any other YAML file, such as `.github/labeler.yml` or `.github/release.yml`.
+    Yaml,
+}
+
+impl FileKind {
+    /// Detect the kind of a file from the conventional GitHub locations in its path.
+    ///
+    /// Returns `None` for files without a `.yml` or `.yaml` extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        if !path
+            .extension()
+            .is_some_and(|ext| ext == "yml" || ext == "yaml")
+        {
+            return None;
+        }
+
+        let components: Vec<&str> = path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect();
+        let in_github = components
+            .iter()
+            .rposition(|name| *name == ".github")
+            .map(|index| &components[index + 1..]);
+        let file_stem = path.file_stem().and_then(|stem| stem.to_str());
+
+        let kind = match in_github {
+            Some(["workflows", _]) => Self::Workflow,
+            _ if file_stem == Some("action") => Self::Action,
+            Some([_]) if file_stem == Some("dependabot") => Self::Dependabot,
+            Some(["ISSUE_TEMPLATE", _]) if file_stem != Some("config") => Self::IssueForm,
+            _ => Self::Yaml,
+        };
+
+        Some(kind)
+    }
+}
+
+/// Detect the kind of a file from its path, falling back to its top-level keys for YAML files
+/// outside the conventional locations, such as workflow templates.
+pub fn detect_kind(path: &Path, content: &str) -> FileKind {
+    match FileKind::from_path(path) {
+        Some(FileKind::Yaml) | None => {}
+        Some(kind) => return kind,
+    }
+
+    let keys: Vec<&str> = content
+        .lines()
+        .filter(|line| !line.starts_with([' ', '\t', '#', '-']))
+        .filter_map(mapping_key)
+        .collect();
+    let has = |key| keys.contains(&key);
+
+    if has("on") && has("jobs") {
+        FileKind::Workflow
+    } else if has("runs") {
+        FileKind::Action
+    } else if has("version") && has("updates") {
+        FileKind::Dependabot
+    } else if has("body") {
+        FileKind::IssueForm
+    } else {
+        FileKind::Yaml
+    }
+}
@@ -1,7 +1,11 @@
 mod config;
 mod formatter;
+mod kind;
+mod model;
 mod parser;
 
-pub use config::{ConfigError, FormatterConfig};
-pub use formatter::{format_file, format_string};
-pub use parser::FormatError;
+pub use config::{Collection, ConfigError, FormatterConfig, SequenceIndent};
+pub use formatter::{format_file, format_string, format_string_with_kind, verify_format};
+pub use kind::{FileKind, detect_kind};
+pub use model::{Action, Job, Matrix, Spanned, Step, Trigger, Workflow};
+pub use parser::{FormatError, Node, Scalar, ScalarStyle, Span, Value, parse};
@@ -0,0 +1,383 @@
+use std::ops::Deref;
+
+use crate::parser::{FormatError, Node, Span, Value, parse};
+
+/// A value of the source with where it is, so that problems can be reported at it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spanned<T> {
+    pub value: T,
+    pub span: Span,
+}
+
+impl<T> Deref for Spanned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl PartialEq<str> for Spanned<String> {
+    fn eq(&self, other: &str) -> bool {
+        self.value == other
+    }
+}
+
+impl PartialEq<&str> for Spanned<String> {
+    fn eq(&self, other: &&str) -> bool {
+        self.value == *other
+    }
+}
+
+/// A GitHub Actions workflow. Parts of the document that don't match the workflow syntax are
+/// skipped.
+#[derive(Debug, Clone)]
+pub struct Workflow {
+    pub name: Option<Spanned<String>>,
+    /// The events that trigger the workflow, from `on`.
+    pub triggers: Vec<Trigger>,
+    /// The `defaults.run.shell` of the workflow.
+    pub shell: Option<Spanned<String>>,
+    pub jobs: Vec<Job>,
+    /// Where the workflow starts in the source.
+    pub span: Span,
+}
+
+/// A GitHub Actions action, from its `action.yml`.
+#[derive(Debug, Clone)]
+pub struct Action {
+    pub name: Option<Spanned<String>>,
+    /// The runtime of the action, from `runs.using`, such as `node20` or `composite`.
+    pub using: Option<Spanned<String>>,
+    /// The steps of a composite action.
+    pub steps: Vec<Step>,
+    /// Where the action starts in the source.
+    pub span: Span,
+}
+
+/// An event that triggers a workflow.
+#[derive(Debug, Clone)]
+pub struct Trigger {
+    /// The name of the event, such as `push`.
+    pub event: String,
+    pub types: Vec<Spanned<String>>,
+    pub branches: Vec<Spanned<String>>,
+    pub branches_ignore: Vec<Spanned<String>>,
+    pub tags: Vec<Spanned<String>>,
+    pub tags_ignore: Vec<Spanned<String>>,
+    pub paths: Vec<Spanned<String>>,
+    pub paths_ignore: Vec<Spanned<String>>,
+    /// The cron expressions of a `schedule` event.
+    pub cron: Vec<Spanned<String>>,
+    /// Where the event is named in the source.
+    pub span: Span,
+}
+
+/// A job of a workflow.
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub id: String,
+    pub name: Option<Spanned<String>>,
+    /// The jobs that must finish before this one starts.
+    pub needs: Vec<Spanned<String>>,
+    /// The runner labels, from a string, a list or the `labels` of a runner group.
+    pub runs_on: Vec<Spanned<String>>,
+    /// The reusable workflow the job calls.
+    pub uses: Option<Spanned<String>>,
+    /// The `defaults.run.shell` of the job.
+    pub shell: Option<Spanned<String>>,
+    pub matrix: Option<Matrix>,
+    pub steps: Vec<Step>,
+    /// The job as written, for the keys that the model doesn't read.
+    pub node: Node,
+    /// Where the job id is in the source.
+    pub span: Span,
+}
+
+/// A step of a job.
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub id: Option<Spanned<String>>,
+    pub name: Option<Spanned<String>>,
+    /// The action the step runs, such as `actions/checkout@v4`.
+    pub uses: Option<Spanned<String>>,
+    /// The script the step runs.
+    pub run: Option<Spanned<String>>,
+    pub shell: Option<Spanned<String>>,
+    /// The inputs of the action, in source order. Inputs that aren't scalars are skipped.
+    pub with: Vec<(Spanned<String>, Spanned<String>)>,
+    /// The environment variables of the step, in source order. Values that aren't scalars are
+    /// skipped.
+    pub env: Vec<(Spanned<String>, Spanned<String>)>,
+    /// The step as written, for the keys that the model doesn't read.
+    pub node: Node,
+    /// Where the step starts in the source.
+    pub span: Span,
+}
+
+/// The `strategy.matrix` of a job.
+#[derive(Debug, Clone)]
+pub struct Matrix {
+    /// The dimensions of the matrix with their values, in source order.
+    pub dimensions: Vec<(Spanned<String>, Vec<Node>)>,
+    /// The combinations added by `include`.
+    pub include: Vec<Node>,
+    /// The combinations removed by `exclude`.
+    pub exclude: Vec<Node>,
+    /// The expression the whole matrix is read from, such as
+    /// `${{ fromJSON(needs.setup.outputs.matrix) }}`.
+    pub expression: Option<Spanned<String>>,
+    /// Where the matrix starts in the source.
+    pub span: Span,
+}
+
+impl Workflow {
+    /// Parse a workflow from a YAML string.
+    pub fn parse(content: &str) -> Result<Self, FormatError> {
+        parse(content).map(|document| Self::from_node(&document))
+    }
+
+    /// Build a workflow from a parsed document.
+    pub fn from_node(document: &Node) -> Self {
+        let jobs = document
+            .get("jobs")
+            .and_then(Node::as_mapping)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|(id, job)| Some(Job::from_node(id.as_str()?, id.span, job)))
+            .collect();
+
+        Self {
+            name: string(document.get("name")),
+            triggers: document.get("on").map(triggers).unwrap_or_default(),
+            shell: default_shell(document),
+            jobs,
+            span: start(document),
+        }
+    }
+
+    /// The job with the given id.
+    pub fn job(&self, id: &str) -> Option<&Job> {
+        self.jobs.iter().find(|job| job.id == id)
+    }
+
+    /// The trigger for the given event.
+    pub fn trigger(&self, event: &str) -> Option<&Trigger> {
+        self.triggers.iter().find(|trigger| trigger.event == event)
+    }
+
+    /// Every step of every job, with the job it belongs to.
+    pub fn steps(&self) -> impl Iterator<Item = (&Job, &Step)> {
+        self.jobs
+            .iter()
+            .flat_map(|job| job.steps.iter().map(move |step| (job, step)))
+    }
+}
+
+impl Action {
+    /// Parse an action from a YAML string.
+    pub fn parse(content: &str) -> Result<Self, FormatError> {
+        parse(content).map(|document| Self::from_node(&document))
+    }
+
+    /// Build an action from a parsed document.
+    pub fn from_node(document: &Node) -> Self {
+        let runs = document.get("runs");
+
+        Self {
+            name: string(document.get("name")),
+            using: string(runs.and_then(|runs| runs.get("using"))),
+            steps: runs
+                .and_then(|runs| runs.get("steps"))
+                .and_then(Node::as_sequence)
+                .unwrap_or_default()
+                .iter()
+                .map(Step::from_node)
+                .collect(),
+            span: start(document),
+        }
+    }
+}
+
+impl Trigger {
+    /// A trigger without filters.
+    fn new(event: String, span: Span) -> Self {
+        Self {
+            event,
+            types: Vec::new(),
+            branches: Vec::new(),
+            branches_ignore: Vec::new(),
+            tags: Vec::new(),
+            tags_ignore: Vec::new(),
+            paths: Vec::new(),
+            paths_ignore: Vec::new(),
+            cron: Vec::new(),
+            span,
+        }
+    }
+}
+
+impl Job {
+    fn from_node(id: &str, span: Span, job: &Node) -> Self {
+        let runs_on = job.get("runs-on").map_or_else(Vec::new, |runs_on| {
+            if runs_on.as_mapping().is_some() {
+                strings(runs_on.get("labels"))
+            } else {
+                strings(Some(runs_on))
+            }
+        });
+
+        Self {
+            id: id.to_string(),
+            name: string(job.get("name")),
+            needs: strings(job.get("needs")),
+            runs_on,
+            uses: string(job.get("uses")),
+            shell: default_shell(job),
+            matrix: job
+                .get("strategy")
+                .and_then(|strategy| strategy.get("matrix"))
+                .map(Matrix::from_node),
+            steps: job
+                .get("steps")
+                .and_then(Node::as_sequence)
+                .unwrap_or_default()
+                .iter()
+                .map(Step::from_node)
+                .collect(),
+            node: job.clone(),
+            span,
+        }
+    }
+}
+
+impl Step {
+    fn from_node(step: &Node) -> Self {
+        Self {
+            id: string(step.get("id")),
+            name: string(step.get("name")),
+            uses: string(step.get("uses")),
+            run: string(step.get("run")),
+            shell: string(step.get("shell")),
+            with: pairs(step.get("with")),
+            env: pairs(step.get("env")),
+            node: step.clone(),
+            span: start(step),
+        }
+    }
+
+    /// The action the step runs without its version, such as `actions/checkout`.
+    pub fn action(&self) -> Option<&str> {
+        let uses = self.uses.as_deref()?;
+        Some(uses.split_once('@').map_or(uses, |(action, _)| action))
+    }
+
+    /// The input of the action with the given name.
+    pub fn input(&self, name: &str) -> Option<&str> {
+        self.with
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl Matrix {
+    fn from_node(matrix: &Node) -> Self {
+        let mut result = Self {
+            dimensions: Vec::new(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            expression: string(Some(matrix)),
+            span: start(matrix),
+        };
+
+        for (key, value) in matrix.as_mapping().unwrap_or_default() {
+            let values = value.as_sequence().unwrap_or_default().to_vec();
+            match key.as_str() {
+                Some("include") => result.include = values,
+                Some("exclude") => result.exclude = values,
+                _ => result
+                    .dimensions
+                    .extend(string(Some(key)).map(|key| (key, values))),
+            }
+        }
+
+        result
+    }
+}
+
+/// The events of `on`, which can be a single event, a list of events or a mapping of events to
+/// their filters.
+fn triggers(on: &Node) -> Vec<Trigger> {
+    match &on.value {
+        Value::Scalar(scalar) => vec![Trigger::new(scalar.value.clone(), on.span)],
+        Value::Sequence(events) => events
+            .iter()
+            .filter_map(|event| Some(Trigger::new(event.as_str()?.to_string(), event.span)))
+            .collect(),
+        Value::Mapping(events) => events
+            .iter()
+            .filter_map(|(event, filters)| {
+                Some(Trigger {
+                    event: event.as_str()?.to_string(),
+                    types: strings(filters.get("types")),
+                    branches: strings(filters.get("branches")),
+                    branches_ignore: strings(filters.get("branches-ignore")),
+                    tags: strings(filters.get("tags")),
+                    tags_ignore: strings(filters.get("tags-ignore")),
+                    paths: strings(filters.get("paths")),
+                    paths_ignore: strings(filters.get("paths-ignore")),
+                    cron: filters
+                        .as_sequence()
+                        .unwrap_or_default()
+                        .iter()
+                        .filter_map(|schedule| string(schedule.get("cron")))
+                        .collect(),
+                    span: event.span,
+                })
+            })
+            .collect(),
+    }
+}
+
+/// Where a node starts, which is the first key of a mapping.
+fn start(node: &Node) -> Span {
+    node.as_mapping()
+        .and_then(|entries| entries.first())
+        .map_or(node.span, |(key, _)| key.span)
+}
+
+/// The `defaults.run.shell` of a workflow or job.
+fn default_shell(node: &Node) -> Option<Spanned<String>> {
+    string(node.get("defaults")?.get("run")?.get("shell"))
+}
+
+/// The value of a scalar node.
+fn string(node: Option<&Node>) -> Option<Spanned<String>> {
+    let node = node?;
+    Some(Spanned {
+        value: node.as_str()?.to_string(),
+        span: node.span,
+    })
+}
+
+/// The values of a node that holds a string or a list of strings.
+fn strings(node: Option<&Node>) -> Vec<Spanned<String>> {
+    let Some(node) = node else {
+        return Vec::new();
+    };
+
+    match node.as_sequence() {
+        Some(items) => items.iter().filter_map(|item| string(Some(item))).collect(),
+        None => string(Some(node)).into_iter().collect(),
+    }
+}
+
+/// The scalar entries of a mapping node.
+fn pairs(node: Option<&Node>) -> Vec<(Spanned<String>, Spanned<String>)> {
+    node.and_then(Node::as_mapping)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|(key, value)| Some((string(Some(key))?, string(Some(value))?)))
+        .collect()
+}
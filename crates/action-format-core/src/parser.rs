@@ -1,4 +1,8 @@
+use std::collections::HashMap;
+
 use thiserror::Error;
+use yaml_rust2::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust2::scanner::{Marker, TScalarStyle};
 
 #[derive(Error, Debug)]
 pub enum FormatError {
@@ -7,4 +11,240 @@ pub enum FormatError {
 
     #[error("Invalid indentation at line {line}: mixed tabs and spaces")]
     MixedIndentation { line: usize },
+
+    #[error("Invalid YAML at line {line}, column {column}: {message}")]
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+
+    #[error("Formatting is not idempotent (this is a bug in action-format, please report it)")]
+    Unstable,
+
+    #[error(
+        "Formatting changed the data at line {line} (this is a bug in action-format, please report it)"
+    )]
+    Changed { line: usize },
+}
+
+/// A location in the source, both 1-indexed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl From<Marker> for Span {
+    fn from(marker: Marker) -> Self {
+        Self {
+            line: marker.line(),
+            column: marker.col() + 1,
+        }
+    }
+}
+
+/// A node of a parsed YAML document.
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub value: Value,
+    /// Where the node starts in the source.
+    pub span: Span,
+}
+
+impl Node {
+    /// The value of the first entry with the given key, if this is a mapping.
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        self.as_mapping()?
+            .iter()
+            .find(|(k, _)| k.as_str() == Some(key))
+            .map(|(_, value)| value)
+    }
+
+    /// The value of a scalar.
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.value {
+            Value::Scalar(scalar) => Some(&scalar.value),
+            _ => None,
+        }
+    }
+
+    /// The items of a sequence.
+    pub fn as_sequence(&self) -> Option<&[Self]> {
+        match &self.value {
+            Value::Sequence(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// The entries of a mapping.
+    pub fn as_mapping(&self) -> Option<&[(Self, Self)]> {
+        match &self.value {
+            Value::Mapping(entries) => Some(entries),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Value {
+    Scalar(Scalar),
+    Sequence(Vec<Node>),
+    Mapping(Vec<(Node, Node)>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Scalar {
+    pub value: String,
+    pub style: ScalarStyle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarStyle {
+    Plain,
+    SingleQuoted,
+    DoubleQuoted,
+    Literal,
+    Folded,
+}
+
+impl From<TScalarStyle> for ScalarStyle {
+    fn from(style: TScalarStyle) -> Self {
+        match style {
+            TScalarStyle::Plain => Self::Plain,
+            TScalarStyle::SingleQuoted => Self::SingleQuoted,
+            TScalarStyle::DoubleQuoted => Self::DoubleQuoted,
+            TScalarStyle::Literal => Self::Literal,
+            TScalarStyle::Folded => Self::Folded,
+        }
+    }
+}
+
+/// Parse the first document of a YAML string. Aliases are replaced by the node they refer to.
+pub fn parse(content: &str) -> Result<Node, FormatError> {
+    Ok(parse_documents(content)?
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| Node {
+            value: Value::Scalar(Scalar {
+                value: String::new(),
+                style: ScalarStyle::Plain,
+            }),
+            span: Span { line: 1, column: 1 },
+        }))
+}
+
+/// Parse every document of a YAML string.
+pub(crate) fn parse_documents(content: &str) -> Result<Vec<Node>, FormatError> {
+    let mut builder = Builder::default();
+    Parser::new_from_str(content)
+        .load(&mut builder, true)
+        .map_err(|err| FormatError::Parse {
+            line: err.marker().line(),
+            column: err.marker().col() + 1,
+            message: err.info().to_string(),
+        })?;
+    Ok(builder.documents)
+}
+
+/// The span of the first node in `before` whose data differs from `after`.
+pub(crate) fn first_difference(before: &Node, after: &Node) -> Option<Span> {
+    match (&before.value, &after.value) {
+        (Value::Scalar(a), Value::Scalar(b)) => {
+            let same = a.value == b.value
+                && (a.style == ScalarStyle::Plain) == (b.style == ScalarStyle::Plain);
+            (!same).then_some(before.span)
+        }
+        (Value::Sequence(a), Value::Sequence(b)) => {
+            if a.len() != b.len() {
+                return Some(before.span);
+            }
+            a.iter().zip(b).find_map(|(a, b)| first_difference(a, b))
+        }
+        (Value::Mapping(a), Value::Mapping(b)) => {
+            if a.len() != b.len() {
+                return Some(before.span);
+            }
+            a.iter().zip(b).find_map(|((ak, av), (bk, bv))| {
+                first_difference(ak, bk).or_else(|| first_difference(av, bv))
+            })
+        }
+        _ => Some(before.span),
+    }
+}
+
+/// Builds [`Node`] trees from parser events.
+#[derive(Default)]
+struct Builder {
+    /// Open collections, with their anchor and a mapping key waiting for its value.
+    stack: Vec<(Node, usize, Option<Node>)>,
+    anchors: HashMap<usize, Node>,
+    documents: Vec<Node>,
+}
+
+impl Builder {
+    fn insert(&mut self, node: Node, anchor: usize) {
+        if anchor > 0 {
+            self.anchors.insert(anchor, node.clone());
+        }
+
+        match self.stack.last_mut() {
+            None => self.documents.push(node),
+            Some((parent, _, key)) => match &mut parent.value {
+                Value::Sequence(items) => items.push(node),
+                Value::Mapping(entries) => match key.take() {
+                    Some(key) => entries.push((key, node)),
+                    None => *key = Some(node),
+                },
+                Value::Scalar(_) => unreachable!("scalars are never pushed on the stack"),
+            },
+        }
+    }
+}
+
+impl MarkedEventReceiver for Builder {
+    fn on_event(&mut self, event: Event, marker: Marker) {
+        let span = Span::from(marker);
+        match event {
+            Event::Scalar(value, style, anchor, _) => {
+                let node = Node {
+                    value: Value::Scalar(Scalar {
+                        value,
+                        style: style.into(),
+                    }),
+                    span,
+                };
+                self.insert(node, anchor);
+            }
+            Event::Alias(anchor) => {
+                if let Some(node) = self.anchors.get(&anchor).cloned() {
+                    self.insert(node, 0);
+                }
+            }
+            Event::SequenceStart(anchor, _) => {
+                let node = Node {
+                    value: Value::Sequence(Vec::new()),
+                    span,
+                };
+                self.stack.push((node, anchor, None));
+            }
+            Event::MappingStart(anchor, _) => {
+                let node = Node {
+                    value: Value::Mapping(Vec::new()),
+                    span,
+                };
+                self.stack.push((node, anchor, None));
+            }
+            Event::SequenceEnd | Event::MappingEnd => {
+                if let Some((node, anchor, _)) = self.stack.pop() {
+                    self.insert(node, anchor);
+                }
+            }
+            Event::Nothing
+            | Event::StreamStart
+            | Event::StreamEnd
+            | Event::DocumentStart
+            | Event::DocumentEnd => {}
+        }
+    }
 }
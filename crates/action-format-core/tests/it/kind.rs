@@ -0,0 +1,139 @@
+use std::path::Path;
+
+use action_format_core::{
+    Collection, FileKind, FormatterConfig, detect_kind, format_string_with_kind,
+};
+
+#[test]
+fn test_kind_from_path() {
+    let cases = [
+        (".github/workflows/ci.yml", Some(FileKind::Workflow)),
+        ("repo/.github/workflows/ci.yaml", Some(FileKind::Workflow)),
+        (".github/workflows/nested/ci.yml", Some(FileKind::Yaml)),
+        ("action.yml", Some(FileKind::Action)),
+        (".github/actions/setup/action.yaml", Some(FileKind::Action)),
+        (".github/dependabot.yml", Some(FileKind::Dependabot)),
+        ("docs/dependabot.yml", Some(FileKind::Yaml)),
+        (".github/ISSUE_TEMPLATE/bug.yml", Some(FileKind::IssueForm)),
+        (".github/ISSUE_TEMPLATE/config.yml", Some(FileKind::Yaml)),
+        (".github/labeler.yml", Some(FileKind::Yaml)),
+        (".github/workflows/README.md", None),
+    ];
+
+    for (path, expected) in cases {
+        assert_eq!(FileKind::from_path(Path::new(path)), expected, "{path}");
+    }
+}
+
+#[test]
+fn test_kind_detect_from_content() {
+    let cases = [
+        ("on: push\njobs: {}\n", FileKind::Workflow),
+        ("name: Setup\nruns:\n  using: composite\n", FileKind::Action),
+        ("version: 2\nupdates: []\n", FileKind::Dependabot),
+        ("name: Bug\nbody: []\n", FileKind::IssueForm),
+        ("# on: push\nlabels: []\n", FileKind::Yaml),
+        ("steps:\n  - on: push\n    jobs: {}\n", FileKind::Yaml),
+    ];
+
+    for (content, expected) in cases {
+        let path = Path::new(".github/workflow-templates/ci.yml");
+        assert_eq!(detect_kind(path, content), expected, "{content}");
+    }
+}
+
+#[test]
+fn test_kind_detect_prefers_path() {
+    // Known locations win over the keys in the file
+    let content = "version: 2\nupdates: []\n";
+    assert_eq!(
+        detect_kind(Path::new(".github/workflows/ci.yml"), content),
+        FileKind::Workflow
+    );
+    assert_eq!(
+        detect_kind(Path::new("action.yml"), content),
+        FileKind::Action
+    );
+
+    // Workflows in subdirectories are only detected by their keys
+    let content = "on: push\njobs: {}\n";
+    assert_eq!(
+        detect_kind(Path::new(".github/workflows/nested/ci.yml"), content),
+        FileKind::Workflow
+    );
+    assert_eq!(
+        detect_kind(Path::new(".github/workflows/nested/data.yml"), "a: 1\n"),
+        FileKind::Yaml
+    );
+}
+
+#[test]
+fn test_kind_format_separates_collections() {
+    let content = r"name: Setup
+inputs:
+  version:
+    description: Version
+  cache:
+    description: Cache
+runs:
+  using: composite
+  steps:
+    - run: make
+      shell: bash
+    - run: make test
+      shell: bash
+updates:
+  - package-ecosystem: cargo
+  - package-ecosystem: npm
+";
+    let config = FormatterConfig {
+        separate: vec![Collection::Steps, Collection::Inputs, Collection::Updates],
+        ..FormatterConfig::default()
+    };
+
+    let action = format_string_with_kind(content, &config, FileKind::Action).unwrap();
+    insta::assert_snapshot!(action, @r"
+    name: Setup
+    inputs:
+      version:
+        description: Version
+
+      cache:
+        description: Cache
+    runs:
+      using: composite
+      steps:
+        - run: make
+          shell: bash
+
+        - run: make test
+          shell: bash
+    updates:
+      - package-ecosystem: cargo
+      - package-ecosystem: npm
+    ");
+
+    let dependabot = format_string_with_kind(content, &config, FileKind::Dependabot).unwrap();
+    insta::assert_snapshot!(dependabot, @r"
+    name: Setup
+    inputs:
+      version:
+        description: Version
+      cache:
+        description: Cache
+    runs:
+      using: composite
+      steps:
+        - run: make
+          shell: bash
+        - run: make test
+          shell: bash
+    updates:
+      - package-ecosystem: cargo
+
+      - package-ecosystem: npm
+    ");
+
+    let yaml = format_string_with_kind(content, &config, FileKind::Yaml).unwrap();
+    assert_eq!(yaml, content);
+}
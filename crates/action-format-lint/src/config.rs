@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use action_format_core::ConfigError;
+
+use crate::rules::RULES;
+
+/// Configuration for the linter, read from the `[lint]` table of the configuration file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct LintConfig {
+    /// Rules to enable (default: all rules)
+    pub select: Vec<RuleSelector>,
+    /// Rules to disable, taking precedence over `select`
+    pub ignore: Vec<RuleSelector>,
+    /// Options for the `unpinned-action` rule
+    pub unpinned_action: UnpinnedActionOptions,
+    /// Options for the `permissions` rule
+    pub permissions: PermissionsOptions,
+    /// Options for the `timeout-minutes` rule
+    pub timeout_minutes: TimeoutMinutesOptions,
+    /// Options for the `deprecated-action` rule
+    pub deprecated_action: DeprecatedActionOptions,
+}
+
+/// Options for the `unpinned-action` rule.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct UnpinnedActionOptions {
+    /// Actions that may be referenced by tag or branch, such as `actions/*` (default: none)
+    pub trusted: Vec<String>,
+}
+
+/// Options for the `permissions` rule.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PermissionsOptions {
+    /// Whether workflows need a top-level `permissions` block (default: true)
+    pub require_top_level: bool,
+}
+
+impl Default for PermissionsOptions {
+    fn default() -> Self {
+        Self {
+            require_top_level: true,
+        }
+    }
+}
+
+/// Options for the `timeout-minutes` rule.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TimeoutMinutesOptions {
+    /// The timeout inserted by the fix, which is only offered when this is set (default: none)
+    pub default: Option<u32>,
+}
+
+/// Options for the `deprecated-action` rule.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct DeprecatedActionOptions {
+    /// Deprecated action versions, such as `actions/upload-artifact@v3`, mapped to their
+    /// replacement, added to the built-in table (default: none)
+    pub versions: BTreeMap<String, String>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            select: vec![RuleSelector("AF".to_string())],
+            ignore: Vec::new(),
+            unpinned_action: UnpinnedActionOptions::default(),
+            permissions: PermissionsOptions::default(),
+            timeout_minutes: TimeoutMinutesOptions::default(),
+            deprecated_action: DeprecatedActionOptions::default(),
+        }
+    }
+}
+
+/// A rule code or a prefix of rule codes, such as `AF001` or `AF00`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct RuleSelector(String);
+
+impl RuleSelector {
+    pub fn matches(&self, code: &str) -> bool {
+        code.starts_with(&self.0)
+    }
+}
+
+impl TryFrom<String> for RuleSelector {
+    type Error = String;
+
+    fn try_from(selector: String) -> Result<Self, Self::Error> {
+        if RULES.iter().any(|rule| rule.code().starts_with(&selector)) && !selector.is_empty() {
+            Ok(Self(selector))
+        } else {
+            Err(format!("unknown rule selector `{selector}`"))
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ConfigFile {
+    #[serde(default)]
+    lint: LintConfig,
+}
+
+impl UnpinnedActionOptions {
+    /// Check if an action may be referenced by tag or branch.
+    pub fn is_trusted(&self, action: &str) -> bool {
+        self.trusted
+            .iter()
+            .any(|pattern| glob_match(pattern, action))
+    }
+}
+
+/// Match `text` against a pattern where `*` matches any sequence of characters.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => {
+            let Some(text) = text.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=text.len())
+                .filter(|&index| text.is_char_boundary(index))
+                .any(|index| glob_match(rest, &text[index..]))
+        }
+    }
+}
+
+impl LintConfig {
+    /// Load the `[lint]` table from a TOML file, falling back to defaults if the file or the
+    /// table doesn't exist.
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path).map_err(|e| ConfigError::Read {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+
+        toml::from_str::<ConfigFile>(&content)
+            .map(|file| file.lint)
+            .map_err(|e| ConfigError::Parse {
+                path: path.to_path_buf(),
+                source: e,
+            })
+    }
+
+    /// Check if the rule with the given code is enabled.
+    pub fn is_enabled(&self, code: &str) -> bool {
+        self.select.iter().any(|selector| selector.matches(code))
+            && !self.ignore.iter().any(|selector| selector.matches(code))
+    }
+}
@@ -0,0 +1,321 @@
+const MONTHS: &[&str] = &[
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const WEEKDAYS: &[&str] = &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+const MONTH_NAMES: &[&str] = &[
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const WEEKDAY_NAMES: &[&str] = &[
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// A field of a cron expression, with the values it accepts and the names that can be used
+/// instead of numbers.
+struct Field {
+    name: &'static str,
+    min: u8,
+    max: u8,
+    names: &'static [&'static str],
+}
+
+const FIELDS: [Field; 5] = [
+    Field {
+        name: "minute",
+        min: 0,
+        max: 59,
+        names: &[],
+    },
+    Field {
+        name: "hour",
+        min: 0,
+        max: 23,
+        names: &[],
+    },
+    Field {
+        name: "day of month",
+        min: 1,
+        max: 31,
+        names: &[],
+    },
+    Field {
+        name: "month",
+        min: 1,
+        max: 12,
+        names: MONTHS,
+    },
+    Field {
+        name: "day of week",
+        min: 0,
+        max: 6,
+        names: WEEKDAYS,
+    },
+];
+
+/// A parsed POSIX cron expression, as used by `on.schedule`, with the values each field matches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Schedule {
+    minutes: Vec<u8>,
+    hours: Vec<u8>,
+    days: Vec<u8>,
+    months: Vec<u8>,
+    weekdays: Vec<u8>,
+    /// Whether the day of month and day of week fields are restricted, in which case a day
+    /// matches if either field matches.
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl Schedule {
+    /// Parse a cron expression with five fields, returning a message describing the problem if
+    /// GitHub won't accept it.
+    pub(crate) fn parse(expression: &str) -> Result<Self, String> {
+        let expression = expression.trim();
+        if expression.starts_with('@') {
+            return Err(format!(
+                "`{expression}` isn't supported, use a cron expression with 5 fields"
+            ));
+        }
+        if let Some((variable, _)) = expression
+            .split_once('=')
+            .filter(|(variable, _)| matches!(*variable, "TZ" | "CRON_TZ"))
+        {
+            return Err(format!(
+                "Schedules always run in UTC, `{variable}` isn't supported"
+            ));
+        }
+
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != FIELDS.len() {
+            return Err(format!(
+                "Cron expression `{expression}` has {} fields, expected 5: minute, hour, day of month, month and day of week",
+                fields.len()
+            ));
+        }
+
+        let mut values = Vec::new();
+        for (text, field) in fields.iter().zip(&FIELDS) {
+            values.push(field.parse(text).ok_or_else(|| {
+                format!(
+                    "Invalid {} `{text}` in cron expression `{expression}`, expected {}",
+                    field.name,
+                    field.expected()
+                )
+            })?);
+        }
+
+        let weekdays = values.pop().unwrap_or_default();
+        let months = values.pop().unwrap_or_default();
+        let days = values.pop().unwrap_or_default();
+        let hours = values.pop().unwrap_or_default();
+        let minutes = values.pop().unwrap_or_default();
+
+        Ok(Self {
+            days_restricted: fields[2] != "*",
+            weekdays_restricted: fields[4] != "*",
+            minutes,
+            hours,
+            days,
+            months,
+            weekdays,
+        })
+    }
+
+    /// The shortest time between two runs, in minutes, ignoring days.
+    pub(crate) fn min_interval(&self) -> u32 {
+        let times: Vec<u32> = self
+            .hours
+            .iter()
+            .flat_map(|&hour| {
+                self.minutes
+                    .iter()
+                    .map(move |&minute| u32::from(hour) * 60 + u32::from(minute))
+            })
+            .collect();
+
+        let wrap = match (times.first(), times.last()) {
+            (Some(first), Some(last)) => first + 24 * 60 - last,
+            _ => 24 * 60,
+        };
+        times
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .chain([wrap])
+            .min()
+            .unwrap_or(24 * 60)
+    }
+
+    /// A human-readable description, such as `at 02:00 UTC every day`.
+    pub(crate) fn describe(&self) -> String {
+        let all_minutes = self.minutes.len() == 60;
+        let all_hours = self.hours.len() == 24;
+
+        let time = if all_minutes && all_hours {
+            "every minute".to_string()
+        } else if all_hours && let Some(step) = step(&self.minutes, 60) {
+            format!("every {step} minutes")
+        } else if all_hours && let [minute] = self.minutes[..] {
+            format!("at minute {minute} of every hour")
+        } else if self.minutes.len() * self.hours.len() <= 4 {
+            let times: Vec<String> = self
+                .hours
+                .iter()
+                .flat_map(|hour| {
+                    self.minutes
+                        .iter()
+                        .map(move |minute| format!("{hour:02}:{minute:02}"))
+                })
+                .collect();
+            format!("at {} UTC", list(&times))
+        } else {
+            format!(
+                "at minute {} past hour {} UTC",
+                ranges(&self.minutes, |value| value.to_string()),
+                ranges(&self.hours, |value| value.to_string())
+            )
+        };
+
+        let weekday = |value: u8| WEEKDAY_NAMES[usize::from(value)].to_string();
+        let days = format!(
+            "{} {} of the month",
+            if self.days.len() == 1 { "day" } else { "days" },
+            ranges(&self.days, |value| value.to_string())
+        );
+        let day = match (self.days_restricted, self.weekdays_restricted) {
+            (false, false) => "every day".to_string(),
+            (false, true) => format!("on {}", ranges(&self.weekdays, weekday)),
+            (true, false) => format!("on {days}"),
+            (true, true) => format!("on {days} or on {}", ranges(&self.weekdays, weekday)),
+        };
+
+        let mut description = if day == "every day" && time.starts_with("every") {
+            time
+        } else {
+            format!("{time} {day}")
+        };
+        if self.months.len() < 12 {
+            description.push_str(" in ");
+            description.push_str(&ranges(&self.months, |value| {
+                MONTH_NAMES[usize::from(value) - 1].to_string()
+            }));
+        }
+
+        description
+    }
+}
+
+impl Field {
+    /// The values matched by a field, such as `1-5` or `*/15`, or `None` if it's invalid.
+    fn parse(&self, text: &str) -> Option<Vec<u8>> {
+        let mut values = Vec::new();
+
+        for item in text.split(',') {
+            let (range, step) = match item.split_once('/') {
+                Some((range, step)) => (range, step.parse::<u8>().ok().filter(|&step| step > 0)?),
+                None => (item, 1),
+            };
+            let (start, end) = match range.split_once('-') {
+                _ if range == "*" => (self.min, self.max),
+                Some((start, end)) => (self.value(start)?, self.value(end)?),
+                None if item.contains('/') => (self.value(range)?, self.max),
+                None => {
+                    let value = self.value(range)?;
+                    (value, value)
+                }
+            };
+            if start > end {
+                return None;
+            }
+
+            values.extend((start..=end).step_by(usize::from(step)));
+        }
+
+        values.sort_unstable();
+        values.dedup();
+        Some(values)
+    }
+
+    /// A single value, as a number or a name.
+    fn value(&self, text: &str) -> Option<u8> {
+        let value = if let Ok(value) = text.parse::<u8>() {
+            value
+        } else {
+            let index = self
+                .names
+                .iter()
+                .position(|name| name.eq_ignore_ascii_case(text))?;
+            u8::try_from(index).ok()? + self.min
+        };
+        Some(value).filter(|value| (self.min..=self.max).contains(value))
+    }
+
+    /// The accepted values, for messages.
+    fn expected(&self) -> String {
+        match (self.names.first(), self.names.last()) {
+            (Some(first), Some(last)) => {
+                format!("{}-{} or {first}-{last}", self.min, self.max)
+            }
+            _ => format!("{}-{}", self.min, self.max),
+        }
+    }
+}
+
+/// The step between values that start at 0 and repeat every `step` up to `limit`.
+fn step(values: &[u8], limit: u8) -> Option<u8> {
+    let step = match values {
+        [0, next, ..] => *next,
+        _ => return None,
+    };
+    let expected: Vec<u8> = (0..limit).step_by(usize::from(step)).collect();
+    (step > 1 && values == expected).then_some(step)
+}
+
+/// The values as a list, with runs of three or more written as ranges, such as
+/// `Monday through Friday`.
+fn ranges(values: &[u8], name: impl Fn(u8) -> String) -> String {
+    let mut items = Vec::new();
+    let mut index = 0;
+
+    while index < values.len() {
+        let start = values[index];
+        let mut end = index;
+        while end + 1 < values.len() && values[end + 1] == values[end] + 1 {
+            end += 1;
+        }
+
+        if end - index >= 2 {
+            items.push(format!("{} through {}", name(start), name(values[end])));
+        } else {
+            items.extend(values[index..=end].iter().map(|&value| name(value)));
+        }
+        index = end + 1;
+    }
+
+    list(&items)
+}
+
+/// A list for messages, such as `a, b and c`.
+fn list(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [item] => item.clone(),
+        [init @ .., last] => format!("{} and {last}", init.join(", ")),
+    }
+}
@@ -0,0 +1,33 @@
+use action_format_core::{FileKind, FormatError, Span, parse};
+
+use crate::cron::Schedule;
+use crate::workflow::Model;
+
+/// A description of part of a file, printed by `action-format lint --explain`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Note {
+    pub span: Span,
+    pub message: String,
+}
+
+/// Describe the parts of a YAML string that are hard to read, such as cron expressions.
+pub fn explain_string(content: &str, kind: FileKind) -> Result<Vec<Note>, FormatError> {
+    if kind != FileKind::Workflow {
+        return Ok(Vec::new());
+    }
+
+    let document = parse(content)?;
+    let model = Model::new(&document, kind);
+    Ok(model
+        .crons()
+        .iter()
+        .filter_map(|cron| {
+            let expression = cron.as_str();
+            let schedule = Schedule::parse(expression).ok()?;
+            Some(Note {
+                span: cron.span,
+                message: format!("`{expression}` runs {}", schedule.describe()),
+            })
+        })
+        .collect())
+}
@@ -0,0 +1,113 @@
+use std::ops::Range;
+
+use action_format_core::{Node, Span, Value};
+
+use crate::fix::{offset, span_at};
+use crate::rule::Context;
+
+/// The `${{ }}` expressions in `text`, with the byte range of each expression including its
+/// delimiters and the text between them.
+pub(crate) fn expressions(text: &str) -> Vec<(Range<usize>, &str)> {
+    let mut expressions = Vec::new();
+    let mut start = 0;
+
+    while let Some(open) = text[start..].find("${{") {
+        let open = start + open;
+        let Some(close) = text[open + 3..].find("}}") else {
+            break;
+        };
+        let close = open + 3 + close;
+        expressions.push((open..close + 2, &text[open + 3..close]));
+        start = close + 2;
+    }
+
+    expressions
+}
+
+/// The property paths in an expression, with indexes such as `[0]` replaced by `*` and
+/// string indexes such as `['title']` by the key. Text in string literals is skipped.
+pub(crate) fn references(expression: &str) -> Vec<String> {
+    let mut references = Vec::new();
+    let mut current = String::new();
+    let mut chars = expression.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '-' | '.' | '*' => current.push(c),
+            '\'' => {
+                if !current.is_empty() {
+                    references.push(std::mem::take(&mut current));
+                }
+                chars.by_ref().take_while(|&c| c != '\'').for_each(drop);
+            }
+            '[' if !current.is_empty() => {
+                let index: String = chars.by_ref().take_while(|&c| c != ']').collect();
+                let index = index.trim();
+                match index.strip_prefix(['\'', '"']) {
+                    Some(key) => {
+                        current.push('.');
+                        current.push_str(key.trim_end_matches(['\'', '"']));
+                    }
+                    None => current.push_str(".*"),
+                }
+            }
+            _ => {
+                if !current.is_empty() {
+                    references.push(std::mem::take(&mut current));
+                }
+            }
+        }
+    }
+    if !current.is_empty() {
+        references.push(current);
+    }
+
+    references
+}
+
+/// The property paths referenced by the expressions in the scalars of `node`. The values of
+/// `if` keys are expressions even without `${{ }}`.
+pub(crate) fn collect_references<'a>(
+    node: &'a Node,
+    condition: bool,
+    out: &mut Vec<(&'a Node, String)>,
+) {
+    match &node.value {
+        Value::Scalar(scalar) => {
+            let text = scalar.value.as_str();
+            let expressions: Vec<&str> = if condition && !text.contains("${{") {
+                vec![text]
+            } else {
+                expressions(text)
+                    .into_iter()
+                    .map(|(_, expression)| expression)
+                    .collect()
+            };
+            out.extend(
+                expressions
+                    .into_iter()
+                    .flat_map(references)
+                    .map(|reference| (node, reference)),
+            );
+        }
+        Value::Sequence(items) => {
+            for item in items {
+                collect_references(item, false, out);
+            }
+        }
+        Value::Mapping(entries) => {
+            for (key, value) in entries {
+                collect_references(value, key.as_str() == Some("if"), out);
+            }
+        }
+    }
+}
+
+/// The position of the first `text` after the start of `node`, or of `node` if the reference
+/// is written differently, such as with an index.
+pub(crate) fn reference_span(context: &Context, node: &Node, text: &str) -> Span {
+    let start = offset(context.content, node.span);
+    context.content[start..]
+        .find(text)
+        .map_or(node.span, |index| span_at(context.content, start + index))
+}
@@ -0,0 +1,111 @@
+use action_format_core::{FileKind, FormatError, ScalarStyle, Span, Value, parse};
+
+use crate::fix::{offset, span_at};
+use crate::script::{Language, placeholders, runs};
+use crate::workflow::Model;
+
+/// A `run` script to check with an external tool such as shellcheck.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalScript {
+    /// The shell that runs the script, `bash` or `sh`.
+    pub shell: &'static str,
+    /// The script, with `${{ }}` expressions replaced by placeholders of the same length.
+    pub text: String,
+    /// The position in the file of each character of each line of the script.
+    spans: Vec<Vec<Span>>,
+}
+
+impl ExternalScript {
+    /// The position in the file of a 1-based line and column of the script.
+    pub fn span(&self, line: usize, column: usize) -> Span {
+        let spans = &self.spans[(line.max(1) - 1).min(self.spans.len() - 1)];
+        spans[(column.max(1) - 1).min(spans.len() - 1)]
+    }
+}
+
+/// The bash and sh `run` scripts of a YAML string. Scripts are the parsed values of their
+/// scalars, so that folded lines, escapes and block indentation read as they do when the step
+/// runs.
+pub fn external_scripts(content: &str, kind: FileKind) -> Result<Vec<ExternalScript>, FormatError> {
+    let document = parse(content)?;
+    let model = Model::new(&document, kind);
+
+    Ok(runs(&model)
+        .into_iter()
+        .filter(|run| matches!(Language::from_shell(run.shell), Some(Language::Shell)))
+        .filter_map(|run| {
+            let Value::Scalar(scalar) = &run.value.value else {
+                return None;
+            };
+            let mut start = offset(content, run.value.span);
+            // Skip the header of block scalars whose span starts at it, so that its indicators
+            // aren't matched
+            if matches!(scalar.style, ScalarStyle::Literal | ScalarStyle::Folded)
+                && content[start..].starts_with(['|', '>'])
+                && let Some(index) = content[start..].find('\n')
+            {
+                start += index + 1;
+            }
+            let shell = match run.shell.split_whitespace().next() {
+                Some("bash") => "bash",
+                _ => "sh",
+            };
+            Some(ExternalScript {
+                shell,
+                text: placeholders(&scalar.value),
+                spans: spans(content, start, &scalar.value),
+            })
+        })
+        .collect())
+}
+
+/// The position in `content` of each character of `value`, a scalar whose source starts at
+/// `start`, by line of `value`.
+///
+/// Characters other than whitespace are matched with the next same character in the source,
+/// which skips indentation, quotes and escapes. Whitespace, which folding and escapes may add
+/// or remove, gets the position of the next unmatched character.
+fn spans(content: &str, start: usize, value: &str) -> Vec<Vec<Span>> {
+    let mut lines = vec![Vec::new()];
+    let mut cursor = start;
+    let mut position = span_at(content, start);
+
+    for ch in value.chars() {
+        if !ch.is_whitespace()
+            && let Some(index) = content[cursor..].find(ch)
+        {
+            advance(&mut position, &content[cursor..cursor + index]);
+            cursor += index;
+        }
+
+        if let Some(line) = lines.last_mut() {
+            line.push(position);
+        }
+        if ch == '\n' {
+            lines.push(Vec::new());
+        }
+
+        if !ch.is_whitespace() && content[cursor..].starts_with(ch) {
+            advance(&mut position, &content[cursor..cursor + ch.len_utf8()]);
+            cursor += ch.len_utf8();
+        }
+    }
+
+    // Positions just past the end of the last line
+    if let Some(line) = lines.last_mut() {
+        line.push(position);
+    }
+    lines
+}
+
+/// Move `position` past `text`.
+fn advance(position: &mut Span, text: &str) {
+    for ch in text.chars() {
+        if ch == '\n' {
+            position.line += 1;
+            position.column = 1;
+        } else {
+            position.column += 1;
+        }
+    }
+}
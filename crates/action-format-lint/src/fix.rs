@@ -0,0 +1,182 @@
+use std::ops::Range;
+use std::path::Path;
+
+use action_format_core::{FileKind, FormatError, Span};
+
+use crate::config::LintConfig;
+use crate::lint_string;
+use crate::project::Project;
+
+/// Maximum number of times a file is linted and fixed, for fixes that only apply once the fixes
+/// they overlap with have been applied.
+const MAX_ITERATIONS: usize = 10;
+
+/// Edits that fix the problem reported by a diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    pub edits: Vec<Edit>,
+}
+
+/// A replacement of a byte range of the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    pub range: Range<usize>,
+    pub content: String,
+}
+
+impl Edit {
+    pub fn insertion(offset: usize, content: impl Into<String>) -> Self {
+        Self {
+            range: offset..offset,
+            content: content.into(),
+        }
+    }
+
+    pub fn replacement(range: Range<usize>, content: impl Into<String>) -> Self {
+        Self {
+            range,
+            content: content.into(),
+        }
+    }
+
+    /// Whether the edits touch the same part of the source, including insertions at the same
+    /// offset, whose order would be ambiguous.
+    fn overlaps(&self, other: &Self) -> bool {
+        self.range.start <= other.range.end && other.range.start <= self.range.end
+    }
+}
+
+/// Apply the fixes of the diagnostics reported for a YAML string, returning the fixed content and
+/// the number of fixes applied.
+pub fn fix_string(
+    path: &Path,
+    content: &str,
+    kind: FileKind,
+    config: &LintConfig,
+    project: &Project,
+) -> Result<(String, usize), FormatError> {
+    let mut content = content.to_string();
+    let mut fixed = 0;
+
+    for _ in 0..MAX_ITERATIONS {
+        let diagnostics = lint_string(path, &content, kind, config, project)?;
+        let fixes = diagnostics
+            .into_iter()
+            .filter_map(|diagnostic| diagnostic.fix);
+        let (fixed_content, applied) = apply_fixes(&content, fixes);
+        if applied == 0 {
+            break;
+        }
+
+        content = fixed_content;
+        fixed += applied;
+    }
+
+    Ok((content, fixed))
+}
+
+/// Apply every fix that doesn't overlap a fix applied before it.
+fn apply_fixes(content: &str, fixes: impl IntoIterator<Item = Fix>) -> (String, usize) {
+    let mut accepted: Vec<Edit> = Vec::new();
+    let mut applied = 0;
+
+    for fix in fixes {
+        if fix
+            .edits
+            .iter()
+            .any(|edit| accepted.iter().any(|other| edit.overlaps(other)))
+        {
+            continue;
+        }
+        accepted.extend(fix.edits);
+        applied += 1;
+    }
+
+    accepted.sort_by_key(|edit| edit.range.start);
+
+    let mut output = String::with_capacity(content.len());
+    let mut last = 0;
+    for edit in accepted {
+        output.push_str(&content[last..edit.range.start]);
+        output.push_str(&edit.content);
+        last = edit.range.end;
+    }
+    output.push_str(&content[last..]);
+
+    (output, applied)
+}
+
+/// The line ending of `content`, which text inserted by fixes should use: `\r\n` if the first line
+/// ends with it.
+pub(crate) fn line_ending(content: &str) -> &'static str {
+    match content.find('\n') {
+        Some(index) if content[..index].ends_with('\r') => "\r\n",
+        _ => "\n",
+    }
+}
+
+/// Insert `lines` at `offset`, which is at the start of a line or at the end of a last line
+/// without a line ending, with the line ending of `content`.
+pub(crate) fn insert_lines(content: &str, offset: usize, lines: &[String]) -> Edit {
+    let newline = line_ending(content);
+    let text = lines.join(newline);
+    if offset > 0 && !content[..offset].ends_with('\n') {
+        Edit::insertion(offset, format!("{newline}{text}"))
+    } else {
+        Edit::insertion(offset, format!("{text}{newline}"))
+    }
+}
+
+/// The byte offset of a span.
+pub(crate) fn offset(content: &str, span: Span) -> usize {
+    let line_start: usize = content
+        .split_inclusive('\n')
+        .take(span.line - 1)
+        .map(str::len)
+        .sum();
+    let line = content[line_start..].lines().next().unwrap_or_default();
+
+    line_start
+        + line
+            .char_indices()
+            .nth(span.column - 1)
+            .map_or(line.len(), |(index, _)| index)
+}
+
+/// The span of a byte offset.
+pub(crate) fn span_at(content: &str, offset: usize) -> Span {
+    let before = &content[..offset];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+    Span {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+    }
+}
+
+/// The byte offset just after the last non-blank line of the block that starts on the line of
+/// `span`, which holds the lines indented further than the column of `span`.
+pub(crate) fn block_end(content: &str, span: Span) -> usize {
+    let mut offset = 0;
+    let mut end = 0;
+
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        offset += line.len();
+        if index + 1 < span.line {
+            continue;
+        }
+
+        let text = line.trim_end();
+        if text.trim_start().is_empty() {
+            continue;
+        }
+
+        let indent = text.len() - text.trim_start().len();
+        if index + 1 > span.line && indent < span.column {
+            break;
+        }
+        end = offset;
+    }
+
+    end
+}
@@ -0,0 +1,65 @@
+mod config;
+mod cron;
+mod explain;
+mod expression;
+mod external;
+mod fix;
+mod lock;
+mod pin;
+mod project;
+mod rule;
+mod rules;
+mod schema;
+mod script;
+mod similar;
+mod workflow;
+
+use std::path::Path;
+
+use action_format_core::{FileKind, FormatError, parse};
+
+use crate::workflow::Model;
+
+pub use config::{
+    DeprecatedActionOptions, LintConfig, PermissionsOptions, RuleSelector, TimeoutMinutesOptions,
+    UnpinnedActionOptions,
+};
+pub use explain::{Note, explain_string};
+pub use external::{ExternalScript, external_scripts};
+pub use fix::{Edit, Fix, fix_string};
+pub use lock::{ActionsLock, LockError};
+pub use pin::{Pinned, pin_string};
+pub use project::Project;
+pub use rule::{Context, Diagnostic, Rule, Severity};
+pub use rules::RULES;
+
+/// Run the rules enabled by the configuration on a YAML string of the project, returning the
+/// diagnostics ordered by position.
+pub fn lint_string(
+    path: &Path,
+    content: &str,
+    kind: FileKind,
+    config: &LintConfig,
+    project: &Project,
+) -> Result<Vec<Diagnostic>, FormatError> {
+    let document = parse(content)?;
+    let model = Model::new(&document, kind);
+    let context = Context {
+        path,
+        content,
+        kind,
+        document: &document,
+        model: &model,
+        config,
+        project,
+    };
+
+    let mut diagnostics: Vec<Diagnostic> = RULES
+        .iter()
+        .filter(|rule| config.is_enabled(rule.code()))
+        .flat_map(|rule| rule.check(&context))
+        .collect();
+    diagnostics.sort_by_key(|diagnostic| (diagnostic.span, diagnostic.code));
+
+    Ok(diagnostics)
+}
@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Commit SHAs of action versions, read from a lock file such as `.github/actions.lock`.
+///
+/// Each line maps a repository and tag or branch to a full commit SHA, with `#` comments:
+///
+/// ```text
+/// # owner/repo@ref sha
+/// actions/checkout@v4 b4ffde65f46336ab88eb53be808477a3936bae11
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ActionsLock {
+    shas: HashMap<String, String>,
+}
+
+impl ActionsLock {
+    /// Load a lock file.
+    pub fn from_file(path: &Path) -> Result<Self, LockError> {
+        let content = std::fs::read_to_string(path).map_err(|e| LockError::Read {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+
+        Self::parse(&content).map_err(|(line, message)| LockError::Parse {
+            path: path.to_path_buf(),
+            line,
+            message,
+        })
+    }
+
+    /// Parse the content of a lock file, returning the line and message of the first error.
+    fn parse(content: &str) -> Result<Self, (usize, String)> {
+        let mut shas = HashMap::new();
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.split_once('#').map_or(line, |(line, _)| line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut fields = line.split_whitespace();
+            let (Some(action), Some(sha), None) = (fields.next(), fields.next(), fields.next())
+            else {
+                return Err((index + 1, "expected `owner/repo@ref sha`".to_string()));
+            };
+            if !action.contains('@') {
+                return Err((index + 1, format!("missing `@ref` in `{action}`")));
+            }
+            if sha.len() != 40 || !sha.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+                return Err((index + 1, format!("`{sha}` is not a full commit SHA")));
+            }
+
+            shas.insert(action.to_string(), sha.to_string());
+        }
+
+        Ok(Self { shas })
+    }
+
+    /// The commit SHA of a tag or branch of a repository.
+    pub fn get(&self, repository: &str, reference: &str) -> Option<&str> {
+        self.shas
+            .get(&format!("{repository}@{reference}"))
+            .map(String::as_str)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LockError {
+    #[error("failed to read lock file '{path}'")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("invalid lock file '{path}' at line {line}: {message}")]
+    Parse {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+}
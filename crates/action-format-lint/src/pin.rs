@@ -0,0 +1,107 @@
+use action_format_core::{FileKind, FormatError, Span, parse};
+
+use crate::config::LintConfig;
+use crate::lock::ActionsLock;
+use crate::workflow::{ActionRef, Model};
+
+/// The result of pinning the actions of a file.
+#[derive(Debug, Clone)]
+pub struct Pinned {
+    /// The content with every locked reference replaced by its commit SHA.
+    pub content: String,
+    /// References that are neither pinned, trusted nor in the lock file.
+    pub missing: Vec<(Span, String)>,
+}
+
+/// Replace tag and branch references to actions and reusable workflows with the commit SHAs in
+/// `lock`, adding the replaced reference as a trailing version comment.
+///
+/// Actions trusted by the `unpinned-action` rule are left alone. Existing comments are kept after
+/// the version.
+pub fn pin_string(
+    content: &str,
+    kind: FileKind,
+    lock: &ActionsLock,
+    config: &LintConfig,
+) -> Result<Pinned, FormatError> {
+    let document = parse(content)?;
+    let mut lines: Vec<String> = content.split_inclusive('\n').map(String::from).collect();
+    let mut missing = Vec::new();
+
+    for uses in Model::new(&document, kind).uses() {
+        let value = uses.as_str();
+        let Some(action) = ActionRef::parse(value) else {
+            continue;
+        };
+        if action.is_pinned() || config.unpinned_action.is_trusted(action.action) {
+            continue;
+        }
+
+        let Some(sha) = lock.get(action.repository, action.reference) else {
+            missing.push((
+                uses.span,
+                format!("{}@{}", action.repository, action.reference),
+            ));
+            continue;
+        };
+
+        if let Some(line) = lines.get_mut(uses.span.line - 1) {
+            let pinned = format!("{}@{sha}", action.action);
+            *line = replace_value(line, uses.span.column, value, &pinned, action.reference);
+        }
+    }
+
+    Ok(Pinned {
+        content: lines.concat(),
+        missing,
+    })
+}
+
+/// Replace `value`, which starts at the 1-indexed character `column` of `line` and may be quoted,
+/// and add `# comment` to the end of the line, or to the start of its existing comment.
+fn replace_value(
+    line: &str,
+    column: usize,
+    value: &str,
+    replacement: &str,
+    comment: &str,
+) -> String {
+    let start = line
+        .char_indices()
+        .nth(column - 1)
+        .map_or(line.len(), |(index, _)| index);
+    let rest = &line[start..];
+    let offset = usize::from(rest.starts_with(['"', '\'']));
+    if !rest[offset..].starts_with(value) {
+        return line.to_string();
+    }
+
+    let end = start + offset + value.len();
+    let (body, newline) = match line.strip_suffix('\n') {
+        Some(body) => (body, "\n"),
+        None => (line, ""),
+    };
+    let (body, newline) = match body.strip_suffix('\r') {
+        Some(body) => (body, "\r\n"),
+        None => (body, newline),
+    };
+
+    // An existing comment is kept after the version
+    let prefix = &line[..start + offset];
+    let mut pinned = match body[end..].split_once(" #") {
+        Some((before, existing)) => {
+            format!(
+                "{prefix}{replacement}{before} # {comment} {}",
+                existing.trim_start()
+            )
+        }
+        None => format!(
+            "{prefix}{replacement}{} # {comment}",
+            body[end..].trim_end()
+        ),
+    }
+    .trim_end()
+    .to_string();
+    pinned.push_str(newline);
+    pinned
+}
@@ -0,0 +1,54 @@
+use std::path::{Path, PathBuf};
+
+use action_format_core::{FileKind, Node, detect_kind, parse};
+
+/// The workflows and actions of a repository, parsed before any file is linted so that rules can
+/// check how files use each other, such as calls to local reusable workflows and actions.
+#[derive(Debug, Default)]
+pub struct Project {
+    /// The repository root, or `None` for a project without files.
+    root: Option<PathBuf>,
+    /// The workflows and actions with their paths relative to the root.
+    files: Vec<(PathBuf, Node)>,
+}
+
+impl Project {
+    /// Parse the workflows and actions among `paths`, which are relative to `root`. Files that
+    /// can't be read or parsed are skipped, since linting them reports the error.
+    pub fn load(root: &Path, paths: &[PathBuf]) -> Self {
+        let files = paths
+            .iter()
+            .filter_map(|path| {
+                let content = std::fs::read_to_string(root.join(path)).ok()?;
+                if !matches!(
+                    detect_kind(path, &content),
+                    FileKind::Workflow | FileKind::Action
+                ) {
+                    return None;
+                }
+                Some((path.clone(), parse(&content).ok()?))
+            })
+            .collect();
+
+        Self {
+            root: Some(root.to_path_buf()),
+            files,
+        }
+    }
+
+    /// The parsed workflow or action at a path relative to the root.
+    pub(crate) fn document(&self, path: &Path) -> Option<&Node> {
+        self.files
+            .iter()
+            .find(|(file, _)| file == path)
+            .map(|(_, document)| document)
+    }
+
+    /// Whether a path relative to the root is missing from the repository. Always `false` for a
+    /// project without files, where nothing is known about the repository.
+    pub(crate) fn is_missing(&self, path: &Path) -> bool {
+        self.root
+            .as_ref()
+            .is_some_and(|root| !root.join(path).exists())
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt;
+use std::path::Path;
+
+use action_format_core::{FileKind, Node, Span};
+
+use crate::config::LintConfig;
+use crate::fix::Fix;
+use crate::project::Project;
+use crate::workflow::Model;
+
+/// A check that reports problems in a file.
+pub trait Rule: Sync {
+    /// The code that identifies the rule, such as `AF001`.
+    fn code(&self) -> &'static str;
+
+    /// A short kebab-case name for the rule, such as `duplicate-key`.
+    fn name(&self) -> &'static str;
+
+    /// How serious the problems reported by the rule are.
+    fn severity(&self) -> Severity;
+
+    /// Report the problems in the file.
+    fn check(&self, context: &Context) -> Vec<Diagnostic>;
+
+    /// Create a diagnostic for this rule at `span`.
+    fn diagnostic(&self, span: Span, message: impl Into<String>) -> Diagnostic
+    where
+        Self: Sized,
+    {
+        Diagnostic {
+            code: self.code(),
+            severity: self.severity(),
+            span,
+            message: message.into(),
+            fix: None,
+        }
+    }
+}
+
+/// The file being linted.
+pub struct Context<'a> {
+    pub path: &'a Path,
+    pub content: &'a str,
+    pub kind: FileKind,
+    /// The parsed first document of the file.
+    pub document: &'a Node,
+    /// The workflow or action read from the document.
+    pub(crate) model: &'a Model,
+    pub config: &'a LintConfig,
+    /// The other files of the repository.
+    pub project: &'a Project,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// A likely mistake, which fails the lint run.
+    Error,
+    /// A suspicious pattern, which is reported without failing the lint run.
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => f.write_str("error"),
+            Self::Warning => f.write_str("warning"),
+        }
+    }
+}
+
+/// A problem reported by a rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub code: &'static str,
+    pub severity: Severity,
+    pub span: Span,
+    pub message: String,
+    /// Edits that fix the problem, applied by `action-format lint --fix`.
+    pub fix: Option<Fix>,
+}
+
+impl Diagnostic {
+    #[must_use]
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
@@ -0,0 +1,104 @@
+use std::path::Path;
+
+use action_format_core::Node;
+
+use crate::rule::{Context, Diagnostic, Rule, Severity};
+use crate::similar::similar;
+use crate::workflow::is_named;
+
+/// The names GitHub looks for in the directory of an action.
+const ACTION_FILES: &[&str] = &["action.yml", "action.yaml"];
+
+/// A step running a local action, such as `uses: ./.github/actions/setup`, with `with` keys that
+/// don't match the `inputs` declared in its `action.yml`: inputs that the action doesn't declare
+/// and required inputs without a default that aren't passed.
+pub struct ActionInputs;
+
+impl Rule for ActionInputs {
+    fn code(&self) -> &'static str {
+        "AF013"
+    }
+
+    fn name(&self) -> &'static str {
+        "action-inputs"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &Context) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for step in context.model.steps() {
+            let Some(uses) = &step.uses else {
+                continue;
+            };
+            let name = uses.as_str();
+            let Some(directory) = name.strip_prefix("./").map(Path::new) else {
+                continue;
+            };
+
+            let Some(action) = ACTION_FILES
+                .iter()
+                .find_map(|file| context.project.document(&directory.join(file)))
+            else {
+                if ACTION_FILES
+                    .iter()
+                    .all(|file| context.project.is_missing(&directory.join(file)))
+                {
+                    diagnostics.push(
+                        self.diagnostic(uses.span, format!("Local action `{name}` doesn't exist")),
+                    );
+                }
+                continue;
+            };
+
+            let declared = action
+                .get("inputs")
+                .and_then(Node::as_mapping)
+                .unwrap_or_default();
+            let passed = step
+                .node
+                .get("with")
+                .and_then(Node::as_mapping)
+                .unwrap_or_default();
+
+            for (key, input) in declared {
+                let Some(input_name) = key.as_str() else {
+                    continue;
+                };
+                let required = input.get("required").and_then(Node::as_str) == Some("true");
+                if required
+                    && input.get("default").is_none()
+                    && !passed.iter().any(|(key, _)| is_named(key, input_name))
+                {
+                    diagnostics.push(self.diagnostic(
+                        uses.span,
+                        format!("Required input `{input_name}` of `{name}` isn't passed"),
+                    ));
+                }
+            }
+
+            for (key, _) in passed {
+                let Some(input_name) = key.as_str() else {
+                    continue;
+                };
+                if declared.iter().any(|(key, _)| is_named(key, input_name)) {
+                    continue;
+                }
+
+                let known = declared.iter().filter_map(|(key, _)| key.as_str());
+                let message = match similar(input_name, known) {
+                    Some(similar) => {
+                        format!("`{name}` has no input `{input_name}`, did you mean `{similar}`?")
+                    }
+                    None => format!("`{name}` has no input `{input_name}`"),
+                };
+                diagnostics.push(self.diagnostic(key.span, message));
+            }
+        }
+
+        diagnostics
+    }
+}
@@ -0,0 +1,33 @@
+use crate::cron::Schedule;
+use crate::rule::{Context, Diagnostic, Rule, Severity};
+
+/// A `schedule` cron expression that GitHub rejects: one that doesn't have five fields, has a
+/// value out of range, or uses extensions such as `@daily` or a `CRON_TZ=` time zone, since
+/// schedules always run in UTC.
+pub struct Cron;
+
+impl Rule for Cron {
+    fn code(&self) -> &'static str {
+        "AF014"
+    }
+
+    fn name(&self) -> &'static str {
+        "cron"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &Context) -> Vec<Diagnostic> {
+        context
+            .model
+            .crons()
+            .iter()
+            .filter_map(|cron| {
+                let message = Schedule::parse(cron).err()?;
+                Some(self.diagnostic(cron.span, message))
+            })
+            .collect()
+    }
+}
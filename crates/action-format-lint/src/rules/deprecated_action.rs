@@ -0,0 +1,137 @@
+use crate::rule::{Context, Diagnostic, Rule, Severity};
+use crate::workflow::{ActionRef, Model, version_comment};
+
+/// Action versions that run on a Node.js version GitHub no longer supports, or that GitHub
+/// stopped serving, with the version that replaces them.
+const DEPRECATED: &[(&str, &str)] = &[
+    ("actions/cache@v1", "actions/cache@v4"),
+    ("actions/cache@v2", "actions/cache@v4"),
+    ("actions/cache@v3", "actions/cache@v4"),
+    ("actions/checkout@v1", "actions/checkout@v4"),
+    ("actions/checkout@v2", "actions/checkout@v4"),
+    ("actions/checkout@v3", "actions/checkout@v4"),
+    (
+        "actions/download-artifact@v1",
+        "actions/download-artifact@v4",
+    ),
+    (
+        "actions/download-artifact@v2",
+        "actions/download-artifact@v4",
+    ),
+    (
+        "actions/download-artifact@v3",
+        "actions/download-artifact@v4",
+    ),
+    ("actions/github-script@v1", "actions/github-script@v7"),
+    ("actions/github-script@v2", "actions/github-script@v7"),
+    ("actions/github-script@v3", "actions/github-script@v7"),
+    ("actions/github-script@v4", "actions/github-script@v7"),
+    ("actions/github-script@v5", "actions/github-script@v7"),
+    ("actions/github-script@v6", "actions/github-script@v7"),
+    ("actions/setup-go@v1", "actions/setup-go@v5"),
+    ("actions/setup-go@v2", "actions/setup-go@v5"),
+    ("actions/setup-go@v3", "actions/setup-go@v5"),
+    ("actions/setup-go@v4", "actions/setup-go@v5"),
+    ("actions/setup-java@v1", "actions/setup-java@v4"),
+    ("actions/setup-java@v2", "actions/setup-java@v4"),
+    ("actions/setup-java@v3", "actions/setup-java@v4"),
+    ("actions/setup-node@v1", "actions/setup-node@v4"),
+    ("actions/setup-node@v2", "actions/setup-node@v4"),
+    ("actions/setup-node@v3", "actions/setup-node@v4"),
+    ("actions/setup-python@v1", "actions/setup-python@v5"),
+    ("actions/setup-python@v2", "actions/setup-python@v5"),
+    ("actions/setup-python@v3", "actions/setup-python@v5"),
+    ("actions/setup-python@v4", "actions/setup-python@v5"),
+    ("actions/upload-artifact@v1", "actions/upload-artifact@v4"),
+    ("actions/upload-artifact@v2", "actions/upload-artifact@v4"),
+    ("actions/upload-artifact@v3", "actions/upload-artifact@v4"),
+];
+
+/// Node.js runtimes that actions can no longer run on.
+const DEPRECATED_RUNTIMES: &[&str] = &["node12", "node16"];
+
+/// A deprecated action version, from a built-in table extended by the `versions` option, matched
+/// against the tag or the version comment of a pinned SHA. Actions in the repository that run on
+/// an unsupported Node.js version are reported too.
+pub struct DeprecatedAction;
+
+impl Rule for DeprecatedAction {
+    fn code(&self) -> &'static str {
+        "AF010"
+    }
+
+    fn name(&self) -> &'static str {
+        "deprecated-action"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, context: &Context) -> Vec<Diagnostic> {
+        let versions = &context.config.deprecated_action.versions;
+        let table: Vec<(&str, &str)> = DEPRECATED
+            .iter()
+            .copied()
+            .filter(|(deprecated, _)| !versions.contains_key(*deprecated))
+            .chain(
+                versions
+                    .iter()
+                    .map(|(deprecated, replacement)| (deprecated.as_str(), replacement.as_str())),
+            )
+            .collect();
+
+        let mut diagnostics: Vec<Diagnostic> = context
+            .model
+            .uses()
+            .into_iter()
+            .filter_map(|uses| {
+                let action = ActionRef::parse(uses)?;
+                let version = if action.is_pinned() {
+                    let line = context.content.lines().nth(uses.span.line - 1)?;
+                    version_comment(line)?
+                } else {
+                    action.reference
+                };
+
+                let (deprecated, replacement) = table
+                    .iter()
+                    .find(|(deprecated, _)| matches(deprecated, action.action, version))?;
+                let message = if replacement.is_empty() {
+                    format!("`{deprecated}` is deprecated")
+                } else {
+                    format!("`{deprecated}` is deprecated, use `{replacement}` instead")
+                };
+                Some(self.diagnostic(uses.span, message))
+            })
+            .collect();
+
+        if let Model::Action(action) = context.model
+            && let Some(using) = &action.using
+            && DEPRECATED_RUNTIMES.contains(&using.as_str())
+        {
+            let runtime = using.as_str();
+            diagnostics.push(self.diagnostic(
+                using.span,
+                format!("`{runtime}` is no longer supported, use `node20` instead"),
+            ));
+        }
+
+        diagnostics
+    }
+}
+
+/// Whether a table entry such as `actions/checkout@v2` matches an action and its version, which
+/// can be more specific, such as `v2.4.0`. An entry without a version matches every version.
+fn matches(deprecated: &str, action: &str, version: &str) -> bool {
+    let Some((deprecated_action, deprecated_version)) = deprecated.split_once('@') else {
+        return deprecated == action;
+    };
+    if deprecated_action != action {
+        return false;
+    }
+
+    version
+        .strip_prefix(deprecated_version)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
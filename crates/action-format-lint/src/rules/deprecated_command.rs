@@ -0,0 +1,178 @@
+use std::ops::Range;
+
+use action_format_core::{ScalarStyle, Value};
+
+use crate::fix::{Edit, Fix, block_end, offset, span_at};
+use crate::rule::{Context, Diagnostic, Rule, Severity};
+use crate::script::{Language, runs};
+
+/// Workflow commands that were replaced by environment files, with the file that replaces them
+/// and whether the command takes a `name` parameter.
+const COMMANDS: &[Command] = &[
+    Command {
+        name: "set-output",
+        file: "GITHUB_OUTPUT",
+        named: true,
+        disabled: false,
+    },
+    Command {
+        name: "save-state",
+        file: "GITHUB_STATE",
+        named: true,
+        disabled: false,
+    },
+    Command {
+        name: "set-env",
+        file: "GITHUB_ENV",
+        named: true,
+        disabled: true,
+    },
+    Command {
+        name: "add-path",
+        file: "GITHUB_PATH",
+        named: false,
+        disabled: true,
+    },
+];
+
+struct Command {
+    name: &'static str,
+    file: &'static str,
+    named: bool,
+    /// Whether GitHub already refuses to run the command.
+    disabled: bool,
+}
+
+/// A `::set-output`, `::save-state`, `::set-env` or `::add-path` workflow command in a `run`
+/// script, which GitHub deprecated in favor of environment files. The fix rewrites
+/// `echo "::set-output name=x::y"` to `echo "x=y" >> "$GITHUB_OUTPUT"` in bash and PowerShell
+/// scripts.
+pub struct DeprecatedCommand;
+
+impl Rule for DeprecatedCommand {
+    fn code(&self) -> &'static str {
+        "AF009"
+    }
+
+    fn name(&self) -> &'static str {
+        "deprecated-command"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, context: &Context) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for run in runs(context.model) {
+            let Value::Scalar(scalar) = &run.value.value else {
+                continue;
+            };
+
+            let language = Language::from_shell(run.shell)
+                .filter(|_| matches!(scalar.style, ScalarStyle::Plain | ScalarStyle::Literal));
+
+            let start = offset(context.content, run.value.span);
+            let end = block_end(context.content, run.key.span).max(start);
+            let source = &context.content[start..end];
+
+            for command in COMMANDS {
+                let pattern = format!("::{}", command.name);
+                for (index, _) in source.match_indices(&pattern) {
+                    let status = if command.disabled {
+                        "disabled"
+                    } else {
+                        "deprecated"
+                    };
+                    let mut diagnostic = self.diagnostic(
+                        span_at(context.content, start + index),
+                        format!(
+                            "`::{}` is {status}, write to `${}` instead",
+                            command.name, command.file
+                        ),
+                    );
+                    if let Some(language) = language
+                        && let Some((range, replacement)) =
+                            rewrite(source, index, command, language)
+                    {
+                        diagnostic = diagnostic.with_fix(Fix {
+                            edits: vec![Edit::replacement(
+                                start + range.start..start + range.end,
+                                replacement,
+                            )],
+                        });
+                    }
+                    diagnostics.push(diagnostic);
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// The range and replacement that turn `echo "::set-output name=x::y"`, where the command starts
+/// at `index`, into `echo "x=y" >> "$GITHUB_OUTPUT"`, if the command is the whole argument of an
+/// `echo` at the end of its line.
+fn rewrite(
+    source: &str,
+    index: usize,
+    command: &Command,
+    language: Language,
+) -> Option<(Range<usize>, String)> {
+    let line_start = source[..index].rfind('\n').map_or(0, |start| start + 1);
+    let line_end = source[index..]
+        .find('\n')
+        .map_or(source.len(), |end| index + end);
+
+    let before = &source[line_start..index];
+    let quote = before
+        .chars()
+        .next_back()
+        .filter(|quote| matches!(quote, '"' | '\''));
+    let argument_start = index - quote.map_or(0, char::len_utf8);
+    let prefix = &source[line_start..argument_start];
+    let head = prefix.trim_end().strip_suffix("echo")?;
+    if prefix.trim_end().len() == prefix.len()
+        || head
+            .chars()
+            .next_back()
+            .is_some_and(|c| !c.is_whitespace() && !matches!(c, ';' | '&' | '|' | '('))
+    {
+        return None;
+    }
+
+    let rest = source[index..line_end].trim_end();
+    let (body, argument_end) = match quote {
+        Some(quote) => {
+            let body = rest.strip_suffix(quote)?;
+            (body, index + rest.len())
+        }
+        None if rest.contains([';', '&', '|', '>', '<', '#']) => return None,
+        None => (rest, index + rest.len()),
+    };
+
+    let body = body.strip_prefix("::")?.strip_prefix(command.name)?;
+    let content = if command.named {
+        let (name, value) = body.strip_prefix(" name=")?.split_once("::")?;
+        format!("{name}={value}")
+    } else {
+        body.strip_prefix("::")?.to_string()
+    };
+    if quote.is_some_and(|quote| content.contains(quote)) {
+        return None;
+    }
+
+    let file = match language {
+        Language::Shell => format!("\"${}\"", command.file),
+        Language::PowerShell => format!("$env:{}", command.file),
+        Language::Cmd | Language::JavaScript => return None,
+    };
+    let quote = quote.map(String::from).unwrap_or_default();
+
+    Some((
+        argument_start..argument_end,
+        format!("{quote}{content}{quote} >> {file}"),
+    ))
+}
@@ -0,0 +1,58 @@
+use action_format_core::{Node, Value};
+
+use crate::rule::{Context, Diagnostic, Rule, Severity};
+
+/// A key that appears more than once in a mapping. GitHub rejects such files, and other YAML
+/// tools silently keep only one of the values.
+pub struct DuplicateKey;
+
+impl Rule for DuplicateKey {
+    fn code(&self) -> &'static str {
+        "AF001"
+    }
+
+    fn name(&self) -> &'static str {
+        "duplicate-key"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &Context) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        self.visit(context.document, &mut diagnostics);
+        diagnostics
+    }
+}
+
+impl DuplicateKey {
+    fn visit(&self, node: &Node, diagnostics: &mut Vec<Diagnostic>) {
+        match &node.value {
+            Value::Scalar(_) => {}
+            Value::Sequence(items) => {
+                for item in items {
+                    self.visit(item, diagnostics);
+                }
+            }
+            Value::Mapping(entries) => {
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    if let Value::Scalar(scalar) = &key.value {
+                        let duplicate = entries[..index].iter().any(|(other, _)| {
+                            matches!(&other.value, Value::Scalar(other) if other.value == scalar.value)
+                        });
+                        if duplicate {
+                            diagnostics.push(
+                                self.diagnostic(
+                                    key.span,
+                                    format!("Duplicate key `{}`", scalar.value),
+                                ),
+                            );
+                        }
+                    }
+                    self.visit(value, diagnostics);
+                }
+            }
+        }
+    }
+}
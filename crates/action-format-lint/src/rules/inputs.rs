@@ -0,0 +1,201 @@
+use action_format_core::{FileKind, Node};
+
+use crate::expression::{collect_references, reference_span};
+use crate::rule::{Context, Diagnostic, Rule, Severity};
+use crate::schema::type_of;
+use crate::workflow::{entry, is_named};
+
+/// Secrets that are available without being declared.
+const BUILTIN_SECRETS: &[&str] = &["GITHUB_TOKEN"];
+
+/// Inputs of `workflow_dispatch` and `workflow_call`, and secrets of `workflow_call`, that are
+/// declared but never used, or used in `inputs.*` and `secrets.*` expressions without being
+/// declared, and input defaults that don't match the input's type. Undeclared secrets are only
+/// reported in workflows that are only triggered by `workflow_call` and declare their secrets,
+/// since other workflows read the repository's secrets.
+pub struct Inputs;
+
+impl Rule for Inputs {
+    fn code(&self) -> &'static str {
+        "AF011"
+    }
+
+    fn name(&self) -> &'static str {
+        "inputs"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &Context) -> Vec<Diagnostic> {
+        if context.kind != FileKind::Workflow {
+            return Vec::new();
+        }
+
+        let on = context.document.get("on");
+        let dispatch = on.and_then(|on| on.get("workflow_dispatch"));
+        let call = on.and_then(|on| on.get("workflow_call"));
+
+        let mut inputs: Vec<(&Node, &Node)> = Vec::new();
+        for trigger in [dispatch, call].into_iter().flatten() {
+            for (key, input) in trigger
+                .get("inputs")
+                .and_then(Node::as_mapping)
+                .unwrap_or_default()
+            {
+                if !inputs.iter().any(|(other, _)| same_name(other, key)) {
+                    inputs.push((key, input));
+                }
+            }
+        }
+        let (secrets, declares_secrets) = match call.and_then(|call| entry(call, "secrets")) {
+            Some((_, secrets)) => (secrets.as_mapping().unwrap_or_default(), true),
+            None => (&[][..], false),
+        };
+
+        let mut references = Vec::new();
+        for (key, value) in context.document.as_mapping().unwrap_or_default() {
+            if key.as_str() != Some("on") {
+                collect_references(value, false, &mut references);
+            }
+        }
+        if let Some(call) = call {
+            collect_references(call, false, &mut references);
+        }
+
+        let mut diagnostics = Vec::new();
+
+        for (key, input) in &inputs {
+            diagnostics.extend(self.check_default(key, input));
+        }
+
+        let only_called = context.model.triggers() == ["workflow_call"];
+        let mut used_inputs: Vec<&str> = Vec::new();
+        let mut used_secrets: Vec<&str> = Vec::new();
+        let mut dynamic_inputs = false;
+        let mut dynamic_secrets = false;
+        let mut reported: Vec<String> = Vec::new();
+
+        for (node, reference) in &references {
+            let segments: Vec<&str> = reference.split('.').collect();
+            let (context_name, name) = match segments.as_slice() {
+                ["inputs" | "secrets", "*", ..] | ["inputs" | "secrets"] => {
+                    if segments[0] == "inputs" {
+                        dynamic_inputs = true;
+                    } else {
+                        dynamic_secrets = true;
+                    }
+                    continue;
+                }
+                ["github", "event", "inputs", name, ..] => ("inputs", *name),
+                [context_name @ ("inputs" | "secrets"), name, ..] => (*context_name, *name),
+                _ => continue,
+            };
+
+            let (declared, used) = if context_name == "inputs" {
+                (
+                    inputs.iter().any(|(key, _)| is_named(key, name)),
+                    &mut used_inputs,
+                )
+            } else {
+                (
+                    secrets.iter().any(|(key, _)| is_named(key, name))
+                        || BUILTIN_SECRETS.contains(&name)
+                        || !(only_called && declares_secrets),
+                    &mut used_secrets,
+                )
+            };
+            used.push(name);
+
+            let token = format!("{context_name}.{name}");
+            if declared || reported.contains(&token) {
+                continue;
+            }
+            let kind = if context_name == "inputs" {
+                "Input"
+            } else {
+                "Secret"
+            };
+            diagnostics.push(self.diagnostic(
+                reference_span(context, node, &token),
+                format!("{kind} `{name}` is used but not declared"),
+            ));
+            reported.push(token);
+        }
+
+        if !dynamic_inputs {
+            for (key, _) in &inputs {
+                if let Some(name) = key.as_str()
+                    && !used_inputs
+                        .iter()
+                        .any(|used| used.eq_ignore_ascii_case(name))
+                {
+                    diagnostics.push(self.diagnostic(
+                        key.span,
+                        format!("Input `{name}` is declared but never used"),
+                    ));
+                }
+            }
+        }
+        if !dynamic_secrets {
+            for (key, _) in secrets {
+                if let Some(name) = key.as_str()
+                    && !used_secrets
+                        .iter()
+                        .any(|used| used.eq_ignore_ascii_case(name))
+                {
+                    diagnostics.push(self.diagnostic(
+                        key.span,
+                        format!("Secret `{name}` is declared but never used"),
+                    ));
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+impl Inputs {
+    /// Check that the default of an input matches its type.
+    fn check_default(&self, key: &Node, input: &Node) -> Option<Diagnostic> {
+        let name = key.as_str()?;
+        let ty = input.get("type")?.as_str()?;
+        let default = input.get("default")?;
+        let value = default.as_str()?;
+        if value.contains("${{") {
+            return None;
+        }
+
+        let message = match ty {
+            "boolean" if type_of(default) != "boolean" => {
+                format!("Default `{value}` of boolean input `{name}` is not `true` or `false`")
+            }
+            "number" if type_of(default) != "number" => {
+                format!("Default `{value}` of number input `{name}` is not a number")
+            }
+            "choice" => {
+                let options: Vec<&str> = input
+                    .get("options")
+                    .and_then(Node::as_sequence)
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(Node::as_str)
+                    .collect();
+                if options.contains(&value) {
+                    return None;
+                }
+                format!("Default `{value}` of choice input `{name}` is not one of its options")
+            }
+            _ => return None,
+        };
+
+        Some(self.diagnostic(default.span, message))
+    }
+}
+
+/// Whether two keys name the same input.
+fn same_name(a: &Node, b: &Node) -> bool {
+    b.as_str().is_some_and(|b| is_named(a, b))
+}
@@ -0,0 +1,55 @@
+mod action_inputs;
+mod cron;
+mod deprecated_action;
+mod deprecated_command;
+mod duplicate_key;
+mod inputs;
+mod needs;
+mod permissions;
+mod schedule_interval;
+mod schema;
+mod script_injection;
+mod shell_script;
+mod step_id;
+mod timeout_minutes;
+mod unpinned_action;
+mod workflow_call;
+
+use crate::rule::Rule;
+
+use action_inputs::ActionInputs;
+use cron::Cron;
+use deprecated_action::DeprecatedAction;
+use deprecated_command::DeprecatedCommand;
+use duplicate_key::DuplicateKey;
+use inputs::Inputs;
+use needs::Needs;
+use permissions::Permissions;
+use schedule_interval::ScheduleInterval;
+use schema::Schema;
+use script_injection::ScriptInjection;
+use shell_script::ShellScript;
+use step_id::StepId;
+use timeout_minutes::TimeoutMinutes;
+use unpinned_action::UnpinnedAction;
+use workflow_call::WorkflowCall;
+
+/// Every lint rule, ordered by code.
+pub static RULES: &[&dyn Rule] = &[
+    &DuplicateKey,
+    &UnpinnedAction,
+    &Permissions,
+    &ScriptInjection,
+    &TimeoutMinutes,
+    &Needs,
+    &StepId,
+    &Schema,
+    &DeprecatedCommand,
+    &DeprecatedAction,
+    &Inputs,
+    &WorkflowCall,
+    &ActionInputs,
+    &Cron,
+    &ScheduleInterval,
+    &ShellScript,
+];
@@ -0,0 +1,179 @@
+use action_format_core::{Node, Span};
+
+use crate::expression::{collect_references, reference_span};
+use crate::rule::{Context, Diagnostic, Rule, Severity};
+use crate::similar::similar;
+
+/// Problems with the dependencies between jobs, which GitHub only reports once the workflow
+/// runs: `needs` listing jobs that don't exist or forming a cycle, and `needs.<job>` expressions
+/// for jobs that aren't listed in `needs` or outputs that the job doesn't declare.
+pub struct Needs;
+
+impl Rule for Needs {
+    fn code(&self) -> &'static str {
+        "AF006"
+    }
+
+    fn name(&self) -> &'static str {
+        "needs"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &Context) -> Vec<Diagnostic> {
+        let jobs = context.model.jobs();
+        let graph: Vec<Vec<(&str, Span)>> = jobs
+            .iter()
+            .map(|job| {
+                job.needs
+                    .iter()
+                    .map(|need| (need.as_str(), need.span))
+                    .collect()
+            })
+            .collect();
+
+        let ids: Vec<&str> = jobs.iter().map(|job| job.id.as_str()).collect();
+
+        let mut diagnostics = Vec::new();
+
+        for (id, needs) in ids.iter().zip(&graph) {
+            for (need, span) in needs {
+                if ids.contains(need) {
+                    continue;
+                }
+
+                let message = match similar(need, ids.iter().copied()) {
+                    Some(similar) => {
+                        format!("Job `{id}` needs unknown job `{need}`, did you mean `{similar}`?")
+                    }
+                    None => format!("Job `{id}` needs unknown job `{need}`"),
+                };
+                diagnostics.push(self.diagnostic(*span, message));
+            }
+        }
+
+        for cycle in cycles(&ids, &graph) {
+            let (_, span) = cycle[cycle.len() - 1];
+            let path: Vec<String> = cycle
+                .iter()
+                .map(|(id, _)| format!("`{id}`"))
+                .chain([format!("`{}`", cycle[0].0)])
+                .collect();
+            diagnostics.push(self.diagnostic(
+                span,
+                format!("Jobs form a dependency cycle: {}", path.join(" -> ")),
+            ));
+        }
+
+        for (job, needs) in jobs.iter().zip(&graph) {
+            let id = &job.id;
+            let mut references = Vec::new();
+            collect_references(&job.node, false, &mut references);
+
+            for (node, reference) in references {
+                let segments: Vec<&str> = reference.split('.').collect();
+                let ["needs", need, rest @ ..] = segments.as_slice() else {
+                    continue;
+                };
+                if *need == "*" {
+                    continue;
+                }
+
+                let span = reference_span(context, node, &format!("needs.{need}"));
+                if !needs.iter().any(|(other, _)| other == need) {
+                    diagnostics.push(self.diagnostic(
+                        span,
+                        format!(
+                            "Job `{id}` uses `needs.{need}` but doesn't list `{need}` in `needs`"
+                        ),
+                    ));
+                    continue;
+                }
+
+                let ["outputs", output, ..] = rest else {
+                    continue;
+                };
+                let Some(needed) = jobs.iter().find(|other| other.id == *need) else {
+                    continue;
+                };
+                if needed.uses.is_some() || *output == "*" {
+                    continue;
+                }
+
+                let declared = needed
+                    .node
+                    .get("outputs")
+                    .and_then(Node::as_mapping)
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|(key, _)| key.as_str() == Some(output));
+                if !declared {
+                    diagnostics.push(
+                        self.diagnostic(span, format!("Job `{need}` has no output `{output}`")),
+                    );
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// The cycles in the dependency graph, each as the jobs in the cycle with the span of the `needs`
+/// entry that leads to the next job, found in the order of the jobs.
+fn cycles<'a>(jobs: &[&'a str], graph: &[Vec<(&'a str, Span)>]) -> Vec<Vec<(&'a str, Span)>> {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum State {
+        New,
+        Visiting,
+        Done,
+    }
+
+    fn visit<'a>(
+        index: usize,
+        jobs: &[&'a str],
+        graph: &[Vec<(&'a str, Span)>],
+        states: &mut [State],
+        stack: &mut Vec<(&'a str, Span)>,
+        cycles: &mut Vec<Vec<(&'a str, Span)>>,
+    ) {
+        states[index] = State::Visiting;
+
+        for &(need, span) in &graph[index] {
+            let Some(next) = jobs.iter().position(|id| *id == need) else {
+                continue;
+            };
+            stack.push((jobs[index], span));
+            match states[next] {
+                State::New => visit(next, jobs, graph, states, stack, cycles),
+                State::Visiting => {
+                    let start = stack.iter().rposition(|(id, _)| *id == need).unwrap_or(0);
+                    cycles.push(stack[start..].to_vec());
+                }
+                State::Done => {}
+            }
+            stack.pop();
+        }
+
+        states[index] = State::Done;
+    }
+
+    let mut states = vec![State::New; jobs.len()];
+    let mut cycles = Vec::new();
+    for index in 0..jobs.len() {
+        if states[index] == State::New {
+            visit(
+                index,
+                jobs,
+                graph,
+                &mut states,
+                &mut Vec::new(),
+                &mut cycles,
+            );
+        }
+    }
+
+    cycles
+}
@@ -0,0 +1,78 @@
+use action_format_core::{FileKind, Value};
+
+use crate::rule::{Context, Diagnostic, Rule, Severity};
+
+/// A workflow without a top-level `permissions` block, which gives the `GITHUB_TOKEN` the
+/// repository's default permissions, or permissions that grant more than needed: `write-all`,
+/// or any write scope in a workflow triggered by `pull_request_target`, which runs with access
+/// to secrets for code from forks.
+pub struct Permissions;
+
+impl Rule for Permissions {
+    fn code(&self) -> &'static str {
+        "AF003"
+    }
+
+    fn name(&self) -> &'static str {
+        "permissions"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &Context) -> Vec<Diagnostic> {
+        if context.kind != FileKind::Workflow {
+            return Vec::new();
+        }
+
+        let mut diagnostics = Vec::new();
+        let jobs = context.model.jobs();
+        let top_level = context.document.get("permissions");
+
+        if top_level.is_none()
+            && context.config.permissions.require_top_level
+            && !jobs.iter().all(|job| job.node.get("permissions").is_some())
+        {
+            let span = context
+                .document
+                .as_mapping()
+                .and_then(|entries| entries.first())
+                .map_or(context.document.span, |(key, _)| key.span);
+            diagnostics
+                .push(self.diagnostic(span, "Workflow has no top-level `permissions` block"));
+        }
+
+        let pull_request_target = context.model.triggers().contains(&"pull_request_target");
+        let blocks = top_level
+            .into_iter()
+            .chain(jobs.iter().filter_map(|job| job.node.get("permissions")));
+
+        for permissions in blocks {
+            match &permissions.value {
+                Value::Scalar(scalar) if scalar.value == "write-all" => {
+                    diagnostics.push(self.diagnostic(
+                        permissions.span,
+                        "`write-all` grants every permission, list the scopes that are needed instead",
+                    ));
+                }
+                Value::Mapping(scopes) if pull_request_target => {
+                    for (scope, access) in scopes {
+                        if access.as_str() == Some("write") {
+                            diagnostics.push(self.diagnostic(
+                                access.span,
+                                format!(
+                                    "`{}: write` in a workflow triggered by `pull_request_target`",
+                                    scope.as_str().unwrap_or_default()
+                                ),
+                            ));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        diagnostics
+    }
+}
@@ -0,0 +1,43 @@
+use crate::cron::Schedule;
+use crate::rule::{Context, Diagnostic, Rule, Severity};
+
+/// The shortest interval between scheduled runs that GitHub supports, in minutes.
+const MIN_INTERVAL: u32 = 5;
+
+/// A `schedule` that runs more often than every 5 minutes, which GitHub doesn't support.
+pub struct ScheduleInterval;
+
+impl Rule for ScheduleInterval {
+    fn code(&self) -> &'static str {
+        "AF015"
+    }
+
+    fn name(&self) -> &'static str {
+        "schedule-interval"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, context: &Context) -> Vec<Diagnostic> {
+        context
+            .model
+            .crons()
+            .iter()
+            .filter_map(|cron| {
+                let expression = cron.as_str();
+                let interval = Schedule::parse(expression).ok()?.min_interval();
+                (interval < MIN_INTERVAL).then(|| {
+                    self.diagnostic(
+                        cron.span,
+                        format!(
+                            "`{expression}` runs every {interval} {}, but GitHub runs schedules at most every {MIN_INTERVAL} minutes",
+                            if interval == 1 { "minute" } else { "minutes" }
+                        ),
+                    )
+                })
+            })
+            .collect()
+    }
+}
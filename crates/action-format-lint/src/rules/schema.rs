@@ -0,0 +1,34 @@
+use action_format_core::FileKind;
+
+use crate::rule::{Context, Diagnostic, Rule, Severity};
+use crate::schema::validate_workflow;
+
+/// A workflow that doesn't match the workflow syntax, checked against a schema bundled with
+/// action-format: unknown keys such as `runs_on`, values of the wrong type such as
+/// `timeout-minutes: ten`, and unknown events.
+pub struct Schema;
+
+impl Rule for Schema {
+    fn code(&self) -> &'static str {
+        "AF008"
+    }
+
+    fn name(&self) -> &'static str {
+        "schema"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &Context) -> Vec<Diagnostic> {
+        if context.kind != FileKind::Workflow {
+            return Vec::new();
+        }
+
+        validate_workflow(context.document)
+            .into_iter()
+            .map(|violation| self.diagnostic(violation.span, violation.message))
+            .collect()
+    }
+}
@@ -0,0 +1,305 @@
+use std::ops::Range;
+
+use action_format_core::{Node, ScalarStyle, Value};
+
+use crate::expression::{expressions, references};
+use crate::fix::{Edit, Fix, block_end, insert_lines, offset, span_at};
+use crate::rule::{Context, Diagnostic, Rule, Severity};
+use crate::script::{Language, Quoting, placeholders, quoting, steps};
+use crate::workflow::entry;
+
+/// Contexts that whoever opens an issue, pull request, discussion or comment, or pushes a branch
+/// or commit, can set to any text. `*` matches any key or index.
+const UNTRUSTED: &[&str] = &[
+    "github.head_ref",
+    "github.event.issue.title",
+    "github.event.issue.body",
+    "github.event.pull_request.title",
+    "github.event.pull_request.body",
+    "github.event.pull_request.head.ref",
+    "github.event.pull_request.head.label",
+    "github.event.pull_request.head.repo.default_branch",
+    "github.event.comment.body",
+    "github.event.review.body",
+    "github.event.review_comment.body",
+    "github.event.discussion.title",
+    "github.event.discussion.body",
+    "github.event.pages.*.page_name",
+    "github.event.commits.*.message",
+    "github.event.commits.*.author.email",
+    "github.event.commits.*.author.name",
+    "github.event.head_commit.message",
+    "github.event.head_commit.author.email",
+    "github.event.head_commit.author.name",
+    "github.event.workflow_run.head_branch",
+    "github.event.workflow_run.head_commit.message",
+    "github.event.workflow_run.head_commit.author.email",
+    "github.event.workflow_run.head_commit.author.name",
+];
+
+/// An expression with an attacker-controllable context in a `run` script or an
+/// `actions/github-script` script, which is expanded into the script before it runs and can
+/// inject commands. The fix moves the context to an environment variable of the step.
+pub struct ScriptInjection;
+
+impl Rule for ScriptInjection {
+    fn code(&self) -> &'static str {
+        "AF004"
+    }
+
+    fn name(&self) -> &'static str {
+        "script-injection"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &Context) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for (step, default_shell) in steps(context.model) {
+            if let Some((key, value)) = entry(&step.node, "run") {
+                let shell = step.shell.as_deref().map_or(default_shell, String::as_str);
+                let script = Script {
+                    key,
+                    value,
+                    language: Language::from_shell(shell),
+                    name: "run",
+                };
+                self.check_script(context, &step.node, &script, &mut diagnostics);
+            }
+
+            let github_script = step
+                .uses
+                .as_ref()
+                .is_some_and(|uses| uses.starts_with("actions/github-script@"));
+            if let Some((key, value)) = step
+                .node
+                .get("with")
+                .filter(|_| github_script)
+                .and_then(|with| entry(with, "script"))
+            {
+                let script = Script {
+                    key,
+                    value,
+                    language: Some(Language::JavaScript),
+                    name: "script",
+                };
+                self.check_script(context, &step.node, &script, &mut diagnostics);
+            }
+        }
+
+        diagnostics
+    }
+}
+
+impl ScriptInjection {
+    fn check_script(
+        &self,
+        context: &Context,
+        step: &Node,
+        script: &Script,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let Value::Scalar(scalar) = &script.value.value else {
+            return;
+        };
+
+        let source = {
+            let start = offset(context.content, script.value.span);
+            let end = block_end(context.content, script.key.span).max(start);
+            start..end
+        };
+        let occurrences = expressions(&context.content[source.clone()]);
+
+        let mut reported: Vec<String> = Vec::new();
+        for (_, expression) in expressions(&scalar.value) {
+            for token in untrusted_contexts(expression) {
+                if reported.contains(&token) {
+                    continue;
+                }
+
+                let span = occurrences
+                    .iter()
+                    .find(|(_, source)| untrusted_contexts(source).contains(&token))
+                    .map_or(script.value.span, |(range, _)| {
+                        span_at(context.content, source.start + range.start)
+                    });
+                let mut diagnostic = self.diagnostic(
+                    span,
+                    format!(
+                        "`{token}` is expanded into the `{}` script, pass it in an environment variable instead",
+                        script.name
+                    ),
+                );
+
+                let fixable = matches!(
+                    scalar.style,
+                    ScalarStyle::Plain | ScalarStyle::Literal | ScalarStyle::Folded
+                );
+                if let Some(language) = script.language.filter(|_| fixable)
+                    && let Some(fix) = fix(context, step, source.clone(), &token, language)
+                {
+                    diagnostic = diagnostic.with_fix(fix);
+                }
+
+                diagnostics.push(diagnostic);
+                reported.push(token);
+            }
+        }
+    }
+}
+
+/// A script run by a step.
+struct Script<'a> {
+    key: &'a Node,
+    value: &'a Node,
+    /// The language of the script, if environment variables can be read in it.
+    language: Option<Language>,
+    name: &'static str,
+}
+
+/// Move every `${{ token }}` expression in the `source` range of a script to an environment
+/// variable of `step`, or `None` if the context is also used in other expressions or the
+/// variable can't be added.
+fn fix(
+    context: &Context,
+    step: &Node,
+    source: Range<usize>,
+    token: &str,
+    language: Language,
+) -> Option<Fix> {
+    let entries = step.as_mapping()?;
+    let (first_key, _) = entries.first()?;
+    if context.content[offset(context.content, step.span)..].starts_with('{') {
+        return None;
+    }
+
+    let name = variable_name(token);
+    let value = format!("${{{{ {token} }}}}");
+
+    let mut edits = Vec::new();
+    if let Some((_, env)) = entry(step, "env") {
+        match env.get(&name) {
+            Some(existing) if existing.as_str() == Some(value.as_str()) => {}
+            Some(_) => return None,
+            None => {
+                let (env_key, _) = env.as_mapping()?.last()?;
+                if context.content[offset(context.content, env.span)..].starts_with('{') {
+                    return None;
+                }
+                let indent = " ".repeat(env_key.span.column - 1);
+                edits.push(insert_lines(
+                    context.content,
+                    block_end(context.content, env_key.span),
+                    &[format!("{indent}{name}: {value}")],
+                ));
+            }
+        }
+    } else {
+        let (last_key, _) = entries.last()?;
+        let indent = " ".repeat(first_key.span.column - 1);
+        edits.push(insert_lines(
+            context.content,
+            block_end(context.content, last_key.span),
+            &[
+                format!("{indent}env:"),
+                format!("{indent}  {name}: {value}"),
+            ],
+        ));
+    }
+
+    let text = &context.content[source.clone()];
+    let scanned = placeholders(text);
+    for (range, expression) in expressions(text) {
+        let contexts = untrusted_contexts(expression);
+        if !contexts.iter().any(|context| context == token) {
+            continue;
+        }
+        if expression.trim() != token {
+            return None;
+        }
+
+        let (range, replacement) = replacement(language, text, &scanned, range, &name)?;
+        edits.push(Edit::replacement(
+            source.start + range.start..source.start + range.end,
+            replacement,
+        ));
+    }
+
+    Some(Fix { edits })
+}
+
+/// The range of `text` to replace for the expression at `range`, and the text that reads the
+/// environment variable `name` in its place without changing what the script does, or `None` if
+/// there is none. `scanned` is `text` with its expressions replaced by placeholders.
+fn replacement(
+    language: Language,
+    text: &str,
+    scanned: &str,
+    range: Range<usize>,
+    name: &str,
+) -> Option<(Range<usize>, String)> {
+    let variable = language.variable(name);
+    let before = text[..range.start].chars().next_back();
+    let after = text[range.end..].chars().next();
+
+    // Braces keep the name from running on into the text after it
+    let expanded = match language {
+        _ if !after.is_some_and(|c| c.is_ascii_alphanumeric() || c == '_') => variable.clone(),
+        Language::Shell => format!("${{{name}}}"),
+        Language::PowerShell => format!("${{env:{name}}}"),
+        Language::Cmd | Language::JavaScript => variable.clone(),
+    };
+
+    let replacement = match (language, quoting(language, scanned, range.start)) {
+        (_, Quoting::Comment) | (Language::Cmd, _) => (range, variable),
+        // Unquoted variables are split into words and expanded as globs
+        (Language::Shell, Quoting::Unquoted) => (range, format!("\"{variable}\"")),
+        (Language::Shell, Quoting::Single) => (range, format!("'\"{variable}\"'")),
+        (Language::Shell, Quoting::Double | Quoting::HereDocument { expands: true })
+        | (Language::PowerShell, Quoting::Unquoted | Quoting::Double) => (range, expanded),
+        (Language::JavaScript, Quoting::Single | Quoting::Double | Quoting::Template)
+            if before.is_some_and(|quote| matches!(quote, '\'' | '"' | '`')) && before == after =>
+        {
+            (range.start - 1..range.end + 1, variable)
+        }
+        (Language::JavaScript, Quoting::Template) => (range, format!("${{{variable}}}")),
+        (Language::JavaScript, Quoting::Single) => (range, format!("' + {variable} + '")),
+        (Language::JavaScript, Quoting::Double) => (range, format!("\" + {variable} + \"")),
+        _ => return None,
+    };
+    Some(replacement)
+}
+
+/// The untrusted contexts referenced by an expression, such as `github.event.issue.title`.
+fn untrusted_contexts(expression: &str) -> Vec<String> {
+    references(expression)
+        .into_iter()
+        .filter(|reference| {
+            let segments: Vec<&str> = reference.split('.').collect();
+            UNTRUSTED.iter().any(|pattern| {
+                let pattern: Vec<&str> = pattern.split('.').collect();
+                segments.len() >= pattern.len()
+                    && pattern.iter().zip(&segments).all(|(expected, segment)| {
+                        *expected == "*" || expected.eq_ignore_ascii_case(segment)
+                    })
+            })
+        })
+        .collect()
+}
+
+/// The environment variable for a context, from its last two keys, such as `ISSUE_TITLE` for
+/// `github.event.issue.title`.
+fn variable_name(token: &str) -> String {
+    let segments: Vec<&str> = token
+        .split('.')
+        .filter(|segment| !matches!(*segment, "github" | "event" | "*"))
+        .collect();
+
+    segments[segments.len().saturating_sub(2)..]
+        .join("_")
+        .replace('-', "_")
+        .to_uppercase()
+}
@@ -0,0 +1,308 @@
+use std::ops::Range;
+
+use crate::fix::{Edit, Fix, span_at};
+use crate::rule::{Context, Diagnostic, Rule, Severity};
+use crate::script::{Language, here_document, here_document_end, placeholders, runs};
+
+/// The environment files that steps write to, whose paths are read from these variables.
+const ENVIRONMENT_FILES: &[&str] = &[
+    "GITHUB_OUTPUT",
+    "GITHUB_ENV",
+    "GITHUB_STATE",
+    "GITHUB_PATH",
+    "GITHUB_STEP_SUMMARY",
+];
+
+/// Commands that come before the command they run, such as `then` in `then cd build`.
+const KEYWORDS: &[&str] = &["then", "do", "else", "{", "time"];
+
+/// Keywords whose command is a condition, so its failure doesn't need to stop the script.
+const CONDITIONS: &[&str] = &["if", "elif", "while", "until", "!"];
+
+/// Common mistakes in bash and sh `run` scripts, found without running shellcheck: environment
+/// files such as `$GITHUB_OUTPUT` that aren't quoted, pipelines in bash scripts whose failures are
+/// ignored because `pipefail` isn't set, and `cd` commands that can fail without stopping the
+/// script. `${{ }}` expressions are replaced by placeholders before the script is read. The fix
+/// quotes the environment files.
+pub struct ShellScript;
+
+impl Rule for ShellScript {
+    fn code(&self) -> &'static str {
+        "AF016"
+    }
+
+    fn name(&self) -> &'static str {
+        "shell-script"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, context: &Context) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for run in runs(context.model) {
+            if !matches!(Language::from_shell(run.shell), Some(Language::Shell)) {
+                continue;
+            }
+            let Some(source) = run.source(context.content) else {
+                continue;
+            };
+
+            let text = placeholders(&context.content[source.clone()]);
+            let script = Script::parse(&text);
+            let span = |index: usize| span_at(context.content, source.start + index);
+
+            for range in &script.unquoted {
+                let variable = &text[range.clone()];
+                diagnostics.push(
+                    self.diagnostic(
+                        span(range.start),
+                        format!("Quote `{variable}` to prevent word splitting"),
+                    )
+                    .with_fix(Fix {
+                        edits: vec![
+                            Edit::insertion(source.start + range.start, "\""),
+                            Edit::insertion(source.start + range.end, "\""),
+                        ],
+                    }),
+                );
+            }
+
+            let bash = run.shell.split_whitespace().next() == Some("bash");
+            let mut options = Options::default();
+            match run.shell {
+                // GitHub runs `bash --noprofile --norc -eo pipefail {0}` and `sh -e {0}`.
+                "bash" => options.set(["-eo", "pipefail"].into_iter()),
+                "sh" => options.set(["-e"].into_iter()),
+                shell => options.set(shell.split_whitespace().skip(1)),
+            }
+            let mut reported_pipe = false;
+
+            for command in &script.commands {
+                let words: Vec<&str> = command
+                    .words
+                    .iter()
+                    .map(|word| &text[word.clone()])
+                    .collect();
+                let position = words
+                    .iter()
+                    .position(|word| !KEYWORDS.contains(word) && !CONDITIONS.contains(word))
+                    .unwrap_or(words.len());
+                let condition = words[..position]
+                    .iter()
+                    .any(|word| CONDITIONS.contains(word));
+
+                match words.get(position) {
+                    Some(&"set") => options.set(words[position + 1..].iter().copied()),
+                    Some(&"cd")
+                        if !options.errexit
+                            && !condition
+                            && !matches!(command.end, Some(("||" | "&&", _))) =>
+                    {
+                        diagnostics.push(self.diagnostic(
+                            span(command.words[position].start),
+                            "`cd` can fail without stopping the script, use `cd … || exit`",
+                        ));
+                    }
+                    _ => {}
+                }
+
+                if let Some(("|" | "|&", index)) = command.end
+                    && bash
+                    && !options.pipefail
+                    && !reported_pipe
+                {
+                    reported_pipe = true;
+                    diagnostics.push(self.diagnostic(
+                        span(index),
+                        "Failures in this pipeline are ignored, add `set -euo pipefail` or use `shell: bash`",
+                    ));
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// The shell options that decide which failures stop a script.
+#[derive(Default)]
+struct Options {
+    errexit: bool,
+    pipefail: bool,
+}
+
+impl Options {
+    /// Apply the arguments of `set`, or of a shell such as `bash -eo pipefail {0}`.
+    fn set<'a>(&mut self, mut arguments: impl Iterator<Item = &'a str>) {
+        while let Some(argument) = arguments.next() {
+            let Some((enable, flags)) = argument
+                .strip_prefix('-')
+                .map(|flags| (true, flags))
+                .or_else(|| argument.strip_prefix('+').map(|flags| (false, flags)))
+                .filter(|(_, flags)| !flags.starts_with('-'))
+            else {
+                continue;
+            };
+
+            if flags.contains('e') {
+                self.errexit = enable;
+            }
+            if flags.contains('o') {
+                match arguments.next() {
+                    Some("errexit") => self.errexit = enable,
+                    Some("pipefail") => self.pipefail = enable,
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// The parts of a shell script that the rule looks at.
+#[derive(Default)]
+struct Script {
+    commands: Vec<Command>,
+    /// The environment file variables that aren't in double quotes.
+    unquoted: Vec<Range<usize>>,
+}
+
+/// A simple command, with the operator that ends it and where the operator is.
+struct Command {
+    words: Vec<Range<usize>>,
+    end: Option<(&'static str, usize)>,
+}
+
+impl Script {
+    /// Split a script into commands and words. Quotes, escapes and comments are understood, and
+    /// the bodies of here documents are skipped, but command substitutions and compound commands
+    /// aren't understood.
+    fn parse(text: &str) -> Self {
+        let bytes = text.as_bytes();
+        let mut script = Self::default();
+        let mut words = Vec::new();
+        let mut word: Option<usize> = None;
+        // The delimiters of the here documents whose bodies start on the next line.
+        let mut here_documents = Vec::new();
+        let mut index = 0;
+
+        while index < bytes.len() {
+            let next = bytes.get(index + 1).copied();
+            match bytes[index] {
+                b' ' | b'\t' | b'\r' => {
+                    if let Some(start) = word.take() {
+                        words.push(start..index);
+                    }
+                }
+                b'\\' if next == Some(b'\n') => {
+                    if let Some(start) = word.take() {
+                        words.push(start..index);
+                    }
+                    index += 1;
+                }
+                b'\\' => {
+                    word.get_or_insert(index);
+                    index += 1;
+                }
+                b'#' if word.is_none() => {
+                    index = text[index..]
+                        .find('\n')
+                        .map_or(bytes.len(), |end| index + end);
+                    continue;
+                }
+                b'\'' => {
+                    word.get_or_insert(index);
+                    index = text[index + 1..]
+                        .find('\'')
+                        .map_or(bytes.len(), |end| index + 1 + end);
+                }
+                b'"' => {
+                    word.get_or_insert(index);
+                    index += 1;
+                    while index < bytes.len() && bytes[index] != b'"' {
+                        index += if bytes[index] == b'\\' { 2 } else { 1 };
+                    }
+                }
+                b'$' => {
+                    word.get_or_insert(index);
+                    if let Some(end) = variable(text, index) {
+                        script.unquoted.push(index..end);
+                        index = end;
+                        continue;
+                    }
+                }
+                b'<' if next == Some(b'<') && bytes.get(index + 2) != Some(&b'<') => {
+                    if let Some(start) = word.take() {
+                        words.push(start..index);
+                    }
+                    let (delimiter, end) = here_document(text, index);
+                    here_documents.push(delimiter.replace(['\'', '"', '\\'], ""));
+                    index = end;
+                    continue;
+                }
+                b'&' if next == Some(b'>') || index > 0 && bytes[index - 1] == b'>' => {
+                    word.get_or_insert(index);
+                }
+                b'|' | b'&' | b';' | b'\n' | b'(' | b')' => {
+                    if let Some(start) = word.take() {
+                        words.push(start..index);
+                    }
+                    let operator = match (bytes[index], next) {
+                        (b'|', Some(b'|')) => "||",
+                        (b'|', Some(b'&')) => "|&",
+                        (b'|', _) => "|",
+                        (b'&', Some(b'&')) => "&&",
+                        (b'&', _) => "&",
+                        (b';', Some(b';')) => ";;",
+                        (b';', _) => ";",
+                        (b'(', _) => "(",
+                        (b')', _) => ")",
+                        _ => "\n",
+                    };
+                    script.commands.push(Command {
+                        words: std::mem::take(&mut words),
+                        end: Some((operator, index)),
+                    });
+                    index += operator.len();
+                    if operator == "\n" {
+                        for delimiter in here_documents.drain(..) {
+                            index = here_document_end(text, index, &delimiter);
+                        }
+                    }
+                    continue;
+                }
+                _ => {
+                    word.get_or_insert(index);
+                }
+            }
+            index += 1;
+        }
+
+        if let Some(start) = word {
+            words.push(start..bytes.len().min(index));
+        }
+        script.commands.push(Command { words, end: None });
+        script
+    }
+}
+
+/// The end of the environment file variable that starts at `index`, such as `$GITHUB_OUTPUT` or
+/// `${GITHUB_OUTPUT}`.
+fn variable(text: &str, index: usize) -> Option<usize> {
+    let rest = &text[index + 1..];
+    let (name, length) = if let Some(braced) = rest.strip_prefix('{') {
+        let name = &braced[..braced.find('}')?];
+        (name, name.len() + 2)
+    } else {
+        let end = rest
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(rest.len());
+        (&rest[..end], end)
+    };
+
+    ENVIRONMENT_FILES
+        .contains(&name)
+        .then_some(index + 1 + length)
+}
@@ -0,0 +1,127 @@
+use action_format_core::{Node, Spanned, Step};
+
+use crate::expression::{collect_references, reference_span};
+use crate::rule::{Context, Diagnostic, Rule, Severity};
+use crate::workflow::Model;
+
+/// Step ids used twice in a job, and `steps.<id>` expressions for steps that don't exist in the
+/// job or haven't run yet, in steps, job `outputs` and the `outputs` of composite actions.
+pub struct StepId;
+
+impl Rule for StepId {
+    fn code(&self) -> &'static str {
+        "AF007"
+    }
+
+    fn name(&self) -> &'static str {
+        "step-id"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &Context) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        match context.model {
+            Model::Workflow(workflow) => {
+                for job in &workflow.jobs {
+                    let name = format!("job `{}`", job.id);
+                    let outputs = job.node.get("outputs");
+                    self.check_steps(context, &name, &job.steps, outputs, &mut diagnostics);
+                }
+            }
+            Model::Action(action) => {
+                let outputs = context.document.get("outputs");
+                self.check_steps(
+                    context,
+                    "the action",
+                    &action.steps,
+                    outputs,
+                    &mut diagnostics,
+                );
+            }
+            Model::Other => {}
+        }
+
+        diagnostics
+    }
+}
+
+impl StepId {
+    /// Check `steps`, and the `outputs` that can use all of them.
+    fn check_steps(
+        &self,
+        context: &Context,
+        name: &str,
+        steps: &[Step],
+        outputs: Option<&Node>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let ids: Vec<Option<&Spanned<String>>> =
+            steps.iter().map(|step| step.id.as_ref()).collect();
+
+        for (index, id) in ids.iter().enumerate() {
+            let Some(id) = id else {
+                continue;
+            };
+            let duplicate = ids[..index]
+                .iter()
+                .flatten()
+                .any(|other| other.value == id.value);
+            if duplicate {
+                diagnostics.push(self.diagnostic(
+                    id.span,
+                    format!("Step id `{}` is used more than once in {name}", id.value),
+                ));
+            }
+        }
+
+        let position = |step: &str| ids.iter().position(|id| id.is_some_and(|id| *id == step));
+
+        for (index, step) in steps.iter().enumerate() {
+            let mut references = Vec::new();
+            collect_references(&step.node, false, &mut references);
+
+            for (node, step_id) in step_references(&references) {
+                let message = match position(step_id) {
+                    None => format!("No step in {name} has id `{step_id}`"),
+                    Some(position) if position >= index => {
+                        format!("Step `{step_id}` hasn't run yet when this step runs")
+                    }
+                    Some(_) => continue,
+                };
+                let span = reference_span(context, node, &format!("steps.{step_id}"));
+                diagnostics.push(self.diagnostic(span, message));
+            }
+        }
+
+        let mut references = Vec::new();
+        if let Some(outputs) = outputs {
+            collect_references(outputs, false, &mut references);
+        }
+        for (node, step_id) in step_references(&references) {
+            if position(step_id).is_none() {
+                let span = reference_span(context, node, &format!("steps.{step_id}"));
+                diagnostics
+                    .push(self.diagnostic(span, format!("No step in {name} has id `{step_id}`")));
+            }
+        }
+    }
+}
+
+/// The step ids of `steps.<id>` references.
+fn step_references<'a>(references: &'a [(&'a Node, String)]) -> Vec<(&'a Node, &'a str)> {
+    references
+        .iter()
+        .filter_map(|(node, reference)| {
+            let mut segments = reference.split('.');
+            (segments.next() == Some("steps"))
+                .then(|| segments.next())
+                .flatten()
+                .filter(|id| !id.is_empty() && *id != "*")
+                .map(|id| (*node, id))
+        })
+        .collect()
+}
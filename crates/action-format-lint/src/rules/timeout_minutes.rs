@@ -0,0 +1,78 @@
+use crate::fix::{Fix, block_end, insert_lines, offset};
+use crate::rule::{Context, Diagnostic, Rule, Severity};
+
+/// Keys that come before `timeout-minutes` in a job, in the order of the workflow syntax
+/// documentation.
+const PRECEDING_KEYS: &[&str] = &[
+    "name",
+    "needs",
+    "permissions",
+    "if",
+    "runs-on",
+    "environment",
+    "concurrency",
+    "outputs",
+    "env",
+    "defaults",
+];
+
+/// A job without `timeout-minutes`, which GitHub cancels only after 6 hours. Jobs that call
+/// reusable workflows can't set a timeout. When `default` is configured, the fix inserts
+/// `timeout-minutes` after the keys that come before it.
+pub struct TimeoutMinutes;
+
+impl Rule for TimeoutMinutes {
+    fn code(&self) -> &'static str {
+        "AF005"
+    }
+
+    fn name(&self) -> &'static str {
+        "timeout-minutes"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, context: &Context) -> Vec<Diagnostic> {
+        context
+            .model
+            .jobs()
+            .iter()
+            .filter(|job| job.uses.is_none() && job.node.get("timeout-minutes").is_none())
+            .filter_map(|job| {
+                let entries = job.node.as_mapping()?;
+                let diagnostic = self.diagnostic(
+                    job.span,
+                    format!("Job `{}` has no `timeout-minutes`", job.id),
+                );
+
+                let Some(default) = context.config.timeout_minutes.default else {
+                    return Some(diagnostic);
+                };
+                let Some((first_key, _)) = entries.first() else {
+                    return Some(diagnostic);
+                };
+                if context.content[offset(context.content, job.node.span)..].starts_with('{') {
+                    return Some(diagnostic);
+                }
+
+                let indent = " ".repeat(first_key.span.column - 1);
+                let line = format!("{indent}timeout-minutes: {default}");
+                let position = entries
+                    .iter()
+                    .rfind(|(key, _)| {
+                        key.as_str()
+                            .is_some_and(|key| PRECEDING_KEYS.contains(&key))
+                    })
+                    .map_or_else(
+                        || offset(context.content, first_key.span) - indent.len(),
+                        |(key, _)| block_end(context.content, key.span),
+                    );
+
+                let edit = insert_lines(context.content, position, &[line]);
+                Some(diagnostic.with_fix(Fix { edits: vec![edit] }))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,59 @@
+use crate::rule::{Context, Diagnostic, Rule, Severity};
+use crate::workflow::{ActionRef, version_comment};
+
+/// An action or reusable workflow referenced by a tag or branch instead of a full commit SHA,
+/// which lets its owner change the code that runs. SHA references need a trailing version
+/// comment, such as `actions/checkout@<sha> # v4.1.1`, so readers and update tools know which
+/// release they point to.
+pub struct UnpinnedAction;
+
+impl Rule for UnpinnedAction {
+    fn code(&self) -> &'static str {
+        "AF002"
+    }
+
+    fn name(&self) -> &'static str {
+        "unpinned-action"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &Context) -> Vec<Diagnostic> {
+        let trusted = &context.config.unpinned_action;
+
+        context
+            .model
+            .uses()
+            .into_iter()
+            .filter_map(|uses| {
+                let value = uses.as_str();
+                let action = ActionRef::parse(value)?;
+                if trusted.is_trusted(action.action) {
+                    return None;
+                }
+
+                if !action.is_pinned() {
+                    return Some(self.diagnostic(
+                        uses.span,
+                        format!("`{value}` is not pinned to a full commit SHA"),
+                    ));
+                }
+
+                let line = context.content.lines().nth(uses.span.line - 1)?;
+                if version_comment(line).is_none() {
+                    return Some(self.diagnostic(
+                        uses.span,
+                        format!(
+                            "`{}` is pinned to a commit SHA without a version comment",
+                            action.action
+                        ),
+                    ));
+                }
+
+                None
+            })
+            .collect()
+    }
+}
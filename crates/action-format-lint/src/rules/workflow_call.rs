@@ -0,0 +1,197 @@
+use std::path::Path;
+
+use action_format_core::{Node, Spanned};
+
+use crate::rule::{Context, Diagnostic, Rule, Severity};
+use crate::schema::type_of;
+use crate::similar::similar;
+use crate::workflow::{entry, is_named};
+
+/// A job calling a local reusable workflow with inputs or secrets that don't match the
+/// `workflow_call` interface of that workflow: required inputs and secrets that aren't passed,
+/// inputs and secrets it doesn't declare, and inputs of the wrong type.
+pub struct WorkflowCall;
+
+impl Rule for WorkflowCall {
+    fn code(&self) -> &'static str {
+        "AF012"
+    }
+
+    fn name(&self) -> &'static str {
+        "workflow-call"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &Context) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for job in context.model.jobs() {
+            let Some(uses) = &job.uses else {
+                continue;
+            };
+            let Some(path) = uses.strip_prefix("./") else {
+                continue;
+            };
+            let call = Call {
+                id: &job.id,
+                job: &job.node,
+                uses,
+                name: uses.as_str(),
+            };
+
+            let Some(workflow) = context.project.document(Path::new(path)) else {
+                if context.project.is_missing(Path::new(path)) {
+                    diagnostics.push(self.diagnostic(
+                        uses.span,
+                        format!("Reusable workflow `{}` doesn't exist", call.name),
+                    ));
+                }
+                continue;
+            };
+            let Some(interface) = workflow
+                .get("on")
+                .and_then(|on| entry(on, "workflow_call"))
+                .map(|(_, interface)| interface)
+            else {
+                diagnostics.push(self.diagnostic(
+                    uses.span,
+                    format!("`{}` isn't triggered by `workflow_call`", call.name),
+                ));
+                continue;
+            };
+
+            self.check_inputs(&call, interface, &mut diagnostics);
+            self.check_secrets(&call, interface, &mut diagnostics);
+        }
+
+        diagnostics
+    }
+}
+
+/// A job that calls a reusable workflow.
+struct Call<'a> {
+    id: &'a str,
+    job: &'a Node,
+    uses: &'a Spanned<String>,
+    /// The `uses` value, such as `./.github/workflows/deploy.yml`.
+    name: &'a str,
+}
+
+impl WorkflowCall {
+    fn check_inputs(&self, call: &Call, interface: &Node, diagnostics: &mut Vec<Diagnostic>) {
+        let declared = interface
+            .get("inputs")
+            .and_then(Node::as_mapping)
+            .unwrap_or_default();
+        let passed = call
+            .job
+            .get("with")
+            .and_then(Node::as_mapping)
+            .unwrap_or_default();
+
+        for (key, input) in declared {
+            let Some(name) = key.as_str() else {
+                continue;
+            };
+            if is_required(input) && !passed.iter().any(|(key, _)| is_named(key, name)) {
+                diagnostics.push(self.diagnostic(
+                    call.uses.span,
+                    format!(
+                        "Job `{}` doesn't pass required input `{name}` of `{}`",
+                        call.id, call.name
+                    ),
+                ));
+            }
+        }
+
+        for (key, value) in passed {
+            let Some(name) = key.as_str() else {
+                continue;
+            };
+            let Some((_, input)) = declared.iter().find(|(key, _)| is_named(key, name)) else {
+                diagnostics.push(self.unknown(call, key, name, "input", declared));
+                continue;
+            };
+
+            let is_expression = value.as_str().is_some_and(|value| value.contains("${{"));
+            let message = match input.get("type").and_then(Node::as_str) {
+                _ if is_expression => continue,
+                Some("boolean") if type_of(value) != "boolean" => {
+                    format!(
+                        "Input `{name}` of `{}` must be `true` or `false`",
+                        call.name
+                    )
+                }
+                Some("number") if type_of(value) != "number" => {
+                    format!("Input `{name}` of `{}` must be a number", call.name)
+                }
+                _ => continue,
+            };
+            diagnostics.push(self.diagnostic(value.span, message));
+        }
+    }
+
+    fn check_secrets(&self, call: &Call, interface: &Node, diagnostics: &mut Vec<Diagnostic>) {
+        let secrets = call.job.get("secrets");
+        if secrets.and_then(Node::as_str) == Some("inherit") {
+            return;
+        }
+
+        let declared = interface
+            .get("secrets")
+            .and_then(Node::as_mapping)
+            .unwrap_or_default();
+        let passed = secrets.and_then(Node::as_mapping).unwrap_or_default();
+
+        for (key, secret) in declared {
+            let Some(name) = key.as_str() else {
+                continue;
+            };
+            if is_required(secret) && !passed.iter().any(|(key, _)| is_named(key, name)) {
+                diagnostics.push(self.diagnostic(
+                    call.uses.span,
+                    format!(
+                        "Job `{}` doesn't pass required secret `{name}` of `{}`",
+                        call.id, call.name
+                    ),
+                ));
+            }
+        }
+
+        for (key, _) in passed {
+            let Some(name) = key.as_str() else {
+                continue;
+            };
+            if !declared.iter().any(|(key, _)| is_named(key, name)) {
+                diagnostics.push(self.unknown(call, key, name, "secret", declared));
+            }
+        }
+    }
+
+    /// A diagnostic for an input or secret that the called workflow doesn't declare.
+    fn unknown(
+        &self,
+        call: &Call,
+        key: &Node,
+        name: &str,
+        kind: &str,
+        declared: &[(Node, Node)],
+    ) -> Diagnostic {
+        let message = match similar(name, declared.iter().filter_map(|(key, _)| key.as_str())) {
+            Some(similar) => format!(
+                "`{}` has no {kind} `{name}`, did you mean `{similar}`?",
+                call.name
+            ),
+            None => format!("`{}` has no {kind} `{name}`", call.name),
+        };
+        self.diagnostic(key.span, message)
+    }
+}
+
+/// Whether an input or secret is declared with `required: true`.
+fn is_required(declaration: &Node) -> bool {
+    declaration.get("required").and_then(Node::as_str) == Some("true")
+}
@@ -0,0 +1,383 @@
+use std::sync::LazyLock;
+
+use action_format_core::{Node, ScalarStyle, Span, Value};
+use serde_json::Value as Json;
+
+use crate::similar::similar;
+
+/// The bundled workflow schema, a subset of JSON Schema: `$ref` to `#/definitions/...`, `type`,
+/// `enum`, `anyOf`, `properties`, `additionalProperties`, `required` and `items`. A `title` on an
+/// object or enum names what its keys or values are, such as `event`.
+static WORKFLOW: LazyLock<Json> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("workflow.json")).expect("the workflow schema is valid JSON")
+});
+
+/// A part of a document that doesn't match the schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Violation {
+    pub span: Span,
+    pub message: String,
+}
+
+/// Check a workflow against the bundled schema.
+pub(crate) fn validate_workflow(document: &Node) -> Vec<Violation> {
+    let validator = Validator { root: &WORKFLOW };
+    let mut violations = Vec::new();
+    validator.validate(&WORKFLOW, document, &mut Vec::new(), &mut violations);
+    violations
+}
+
+/// A segment of the path to a node, used to name the node in messages.
+enum Segment<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+/// The path to a node, such as `jobs.build.steps[0]`.
+fn display(path: &[Segment]) -> String {
+    let mut output = String::new();
+    for segment in path {
+        match segment {
+            Segment::Key(key) => {
+                if !output.is_empty() {
+                    output.push('.');
+                }
+                output.push_str(key);
+            }
+            Segment::Index(index) => {
+                output.push('[');
+                output.push_str(&index.to_string());
+                output.push(']');
+            }
+        }
+    }
+    output
+}
+
+/// A node to start messages with, such as `` `jobs.build` ``.
+fn subject(path: &[Segment]) -> String {
+    if path.is_empty() {
+        "The workflow".to_string()
+    } else {
+        format!("`{}`", display(path))
+    }
+}
+
+/// Where a node is, to end messages with, such as `` in `jobs.build` ``.
+fn location(path: &[Segment]) -> String {
+    if path.is_empty() {
+        String::new()
+    } else {
+        format!(" in `{}`", display(path))
+    }
+}
+
+/// The JSON Schema type of a node, where plain scalars are typed like YAML 1.2 core schema
+/// scalars and every other scalar is a string.
+pub(crate) fn type_of(node: &Node) -> &'static str {
+    match &node.value {
+        Value::Mapping(_) => "object",
+        Value::Sequence(_) => "array",
+        Value::Scalar(scalar) if scalar.style != ScalarStyle::Plain => "string",
+        Value::Scalar(scalar) => match scalar.value.as_str() {
+            "" | "~" | "null" | "Null" | "NULL" => "null",
+            "true" | "True" | "TRUE" | "false" | "False" | "FALSE" => "boolean",
+            value
+                if value
+                    .starts_with(|c: char| c.is_ascii_digit() || matches!(c, '-' | '+' | '.'))
+                    && value.parse::<f64>().is_ok() =>
+            {
+                "number"
+            }
+            _ => "string",
+        },
+    }
+}
+
+/// Whether a node of type `actual` matches the schema type `expected`. Any scalar other than
+/// null can be used as a string.
+fn matches_type(actual: &str, expected: &str) -> bool {
+    actual == expected || (expected == "string" && !matches!(actual, "object" | "array" | "null"))
+}
+
+/// A type for messages, such as `a mapping`.
+fn describe(ty: &str) -> &str {
+    match ty {
+        "object" => "a mapping",
+        "array" => "a sequence",
+        "string" => "a string",
+        "number" => "a number",
+        "boolean" => "a boolean",
+        _ => "null",
+    }
+}
+
+/// A suggestion to end messages with, if one of the candidates is close to `name`.
+fn suggestion<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> String {
+    similar(name, candidates)
+        .map(|similar| format!(", did you mean `{similar}`?"))
+        .unwrap_or_default()
+}
+
+/// A list for messages, such as `a, b or c`.
+fn list(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [item] => item.clone(),
+        [init @ .., last] => format!("{} or {last}", init.join(", ")),
+    }
+}
+
+/// Whether a scalar holds a `${{ }}` expression, whose type is only known when the workflow runs.
+fn is_expression(node: &Node) -> bool {
+    matches!(&node.value, Value::Scalar(scalar) if scalar.value.contains("${{"))
+}
+
+struct Validator<'a> {
+    root: &'a Json,
+}
+
+impl<'a> Validator<'a> {
+    /// Follow a `$ref` to the schema it points to.
+    fn resolve(&self, mut schema: &'a Json) -> &'a Json {
+        while let Some(reference) = schema.get("$ref").and_then(Json::as_str) {
+            let pointer = reference.trim_start_matches('#');
+            schema = self
+                .root
+                .pointer(pointer)
+                .unwrap_or_else(|| panic!("the workflow schema has no `{reference}`"));
+        }
+        schema
+    }
+
+    /// The types a schema accepts, or `None` if it accepts any type.
+    fn types(&self, schema: &'a Json) -> Option<Vec<&'a str>> {
+        let schema = self.resolve(schema);
+        if let Some(alternatives) = schema.get("anyOf").and_then(Json::as_array) {
+            let mut types = Vec::new();
+            for alternative in alternatives {
+                for ty in self.types(alternative)? {
+                    if !types.contains(&ty) {
+                        types.push(ty);
+                    }
+                }
+            }
+            return Some(types);
+        }
+
+        match schema.get("type") {
+            Some(Json::String(ty)) => Some(vec![ty.as_str()]),
+            Some(Json::Array(types)) => Some(types.iter().filter_map(Json::as_str).collect()),
+            _ if schema.get("enum").is_some() => Some(vec!["string"]),
+            _ => None,
+        }
+    }
+
+    fn validate<'n>(
+        &self,
+        schema: &'a Json,
+        node: &'n Node,
+        path: &mut Vec<Segment<'n>>,
+        violations: &mut Vec<Violation>,
+    ) {
+        let schema = self.resolve(schema);
+        if is_expression(node) {
+            return;
+        }
+
+        if let Some(alternatives) = schema.get("anyOf").and_then(Json::as_array) {
+            self.validate_any(alternatives, node, path, violations);
+            return;
+        }
+
+        let actual = type_of(node);
+        if let Some(types) = self.types(schema)
+            && !types.iter().any(|expected| matches_type(actual, expected))
+        {
+            let expected: Vec<String> = types.iter().map(|ty| describe(ty).to_string()).collect();
+            violations.push(Violation {
+                span: node.span,
+                message: format!(
+                    "{} must be {}, not {}",
+                    subject(path),
+                    list(&expected),
+                    describe(actual)
+                ),
+            });
+            return;
+        }
+
+        if let Some(values) = schema.get("enum").and_then(Json::as_array) {
+            validate_enum(schema, values, node, path, violations);
+        }
+
+        match &node.value {
+            Value::Mapping(entries) => {
+                self.validate_mapping(schema, node, entries, path, violations);
+            }
+            Value::Sequence(items) => {
+                if let Some(item_schema) = schema.get("items") {
+                    for (index, item) in items.iter().enumerate() {
+                        path.push(Segment::Index(index));
+                        self.validate(item_schema, item, path, violations);
+                        path.pop();
+                    }
+                }
+            }
+            Value::Scalar(_) => {}
+        }
+    }
+
+    /// Check a node against the alternatives of an `anyOf`, reporting the problems of the first
+    /// alternative of the node's type if none of them match.
+    fn validate_any<'n>(
+        &self,
+        alternatives: &'a [Json],
+        node: &'n Node,
+        path: &mut Vec<Segment<'n>>,
+        violations: &mut Vec<Violation>,
+    ) {
+        let actual = type_of(node);
+        let mut first = None;
+
+        for alternative in alternatives {
+            let mut problems = Vec::new();
+            self.validate(alternative, node, path, &mut problems);
+            if problems.is_empty() {
+                return;
+            }
+
+            let same_type = self
+                .types(alternative)
+                .is_none_or(|types| types.iter().any(|expected| matches_type(actual, expected)));
+            if same_type && first.is_none() {
+                first = Some(problems);
+            }
+        }
+
+        if let Some(problems) = first {
+            violations.extend(problems);
+            return;
+        }
+
+        let expected: Vec<String> = alternatives
+            .iter()
+            .filter_map(|alternative| self.types(alternative))
+            .flatten()
+            .map(|ty| describe(ty).to_string())
+            .fold(Vec::new(), |mut expected, ty| {
+                if !expected.contains(&ty) {
+                    expected.push(ty);
+                }
+                expected
+            });
+        violations.push(Violation {
+            span: node.span,
+            message: format!(
+                "{} must be {}, not {}",
+                subject(path),
+                list(&expected),
+                describe(actual)
+            ),
+        });
+    }
+
+    fn validate_mapping<'n>(
+        &self,
+        schema: &'a Json,
+        node: &'n Node,
+        entries: &'n [(Node, Node)],
+        path: &mut Vec<Segment<'n>>,
+        violations: &mut Vec<Violation>,
+    ) {
+        let properties = schema.get("properties").and_then(Json::as_object);
+        let additional = schema.get("additionalProperties");
+
+        for required in schema
+            .get("required")
+            .and_then(Json::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Json::as_str)
+        {
+            if !entries
+                .iter()
+                .any(|(key, _)| key.as_str() == Some(required))
+            {
+                let span = entries.first().map_or(node.span, |(key, _)| key.span);
+                violations.push(Violation {
+                    span,
+                    message: format!("Missing required key `{required}`{}", location(path)),
+                });
+            }
+        }
+
+        for (key, value) in entries {
+            let Some(name) = key.as_str() else {
+                continue;
+            };
+
+            let property = properties.and_then(|properties| properties.get(name));
+            let schema = match (property, additional) {
+                (Some(schema), _) => schema,
+                (None, Some(Json::Bool(false))) => {
+                    let title = schema.get("title").and_then(Json::as_str).unwrap_or("key");
+                    let known = properties
+                        .into_iter()
+                        .flat_map(|properties| properties.keys().map(String::as_str));
+                    violations.push(Violation {
+                        span: key.span,
+                        message: format!(
+                            "Unknown {title} `{name}`{}{}",
+                            location(path),
+                            suggestion(name, known)
+                        ),
+                    });
+                    continue;
+                }
+                (None, Some(schema)) if schema.is_object() => schema,
+                (None, _) => continue,
+            };
+
+            path.push(Segment::Key(name));
+            self.validate(schema, value, path, violations);
+            path.pop();
+        }
+    }
+}
+
+/// Check a scalar against the values of an `enum`.
+fn validate_enum(
+    schema: &Json,
+    values: &[Json],
+    node: &Node,
+    path: &[Segment],
+    violations: &mut Vec<Violation>,
+) {
+    let Some(value) = node.as_str() else {
+        return;
+    };
+    let values: Vec<&str> = values.iter().filter_map(Json::as_str).collect();
+    if values.contains(&value) {
+        return;
+    }
+
+    let message = if let Some(title) = schema.get("title").and_then(Json::as_str) {
+        format!(
+            "Unknown {title} `{value}`{}{}",
+            location(path),
+            suggestion(value, values.iter().copied())
+        )
+    } else {
+        let expected: Vec<String> = values.iter().map(|value| format!("`{value}`")).collect();
+        format!(
+            "`{value}` is not a valid value for `{}`, expected {}",
+            display(path),
+            list(&expected)
+        )
+    };
+    violations.push(Violation {
+        span: node.span,
+        message,
+    });
+}
@@ -0,0 +1,255 @@
+use std::ops::Range;
+
+use action_format_core::{Node, ScalarStyle, Step, Value};
+
+use crate::expression::expressions;
+use crate::fix::{block_end, offset};
+use crate::workflow::{Model, entry};
+
+/// A language that scripts run by steps are written in.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Language {
+    Shell,
+    PowerShell,
+    Cmd,
+    JavaScript,
+}
+
+impl Language {
+    pub(crate) fn from_shell(shell: &str) -> Option<Self> {
+        match shell.split_whitespace().next()? {
+            "bash" | "sh" => Some(Self::Shell),
+            "pwsh" | "powershell" => Some(Self::PowerShell),
+            "cmd" => Some(Self::Cmd),
+            _ => None,
+        }
+    }
+
+    /// How a script reads the environment variable `name`.
+    pub(crate) fn variable(self, name: &str) -> String {
+        match self {
+            Self::Shell => format!("${name}"),
+            Self::PowerShell => format!("$env:{name}"),
+            Self::Cmd => format!("%{name}%"),
+            Self::JavaScript => format!("process.env.{name}"),
+        }
+    }
+}
+
+/// The steps of a workflow or composite action, with the shell that runs their `run` scripts
+/// when they don't set `shell`. Without a default, GitHub runs scripts with `bash -e {0}`, which
+/// unlike `shell: bash` doesn't set `pipefail`.
+pub(crate) fn steps(model: &Model) -> Vec<(&Step, &str)> {
+    match model {
+        Model::Workflow(workflow) => workflow
+            .jobs
+            .iter()
+            .flat_map(|job| {
+                let shell = job
+                    .shell
+                    .as_deref()
+                    .or(workflow.shell.as_deref())
+                    .map_or_else(
+                        || {
+                            let windows =
+                                job.runs_on.iter().any(|runner| runner.contains("windows"));
+                            if windows { "pwsh" } else { "bash -e {0}" }
+                        },
+                        String::as_str,
+                    );
+                job.steps.iter().map(move |step| (step, shell))
+            })
+            .collect(),
+        Model::Action(action) => action.steps.iter().map(|step| (step, "bash")).collect(),
+        Model::Other => Vec::new(),
+    }
+}
+
+/// A `run` script of a step.
+pub(crate) struct Run<'a> {
+    pub(crate) key: &'a Node,
+    pub(crate) value: &'a Node,
+    /// The `shell` of the step, or the default shell.
+    pub(crate) shell: &'a str,
+}
+
+impl Run<'_> {
+    /// The range of the script in `content`, for literal blocks and plain scalars whose source
+    /// lines are the lines of the script. The range of a literal block starts after its `|` line.
+    pub(crate) fn source(&self, content: &str) -> Option<Range<usize>> {
+        let Value::Scalar(scalar) = &self.value.value else {
+            return None;
+        };
+
+        let start = offset(content, self.value.span);
+        let end = block_end(content, self.key.span).max(start);
+        match scalar.style {
+            ScalarStyle::Plain => Some(start..end),
+            ScalarStyle::Literal if content[start..].starts_with('|') => {
+                let start = content[start..end]
+                    .find('\n')
+                    .map_or(end, |index| start + index + 1);
+                Some(start..end)
+            }
+            ScalarStyle::Literal => Some(start..end),
+            _ => None,
+        }
+    }
+}
+
+/// The `run` scripts of a workflow or composite action.
+pub(crate) fn runs(model: &Model) -> Vec<Run<'_>> {
+    steps(model)
+        .into_iter()
+        .filter_map(|(step, default_shell)| {
+            let (key, value) = entry(&step.node, "run")?;
+            let shell = step.shell.as_deref().map_or(default_shell, String::as_str);
+            Some(Run { key, value, shell })
+        })
+        .collect()
+}
+
+/// How the text at an offset of a script is quoted, which decides how it can read a variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Quoting {
+    Unquoted,
+    /// In single quotes, where shells and PowerShell expand nothing.
+    Single,
+    Double,
+    /// In a JavaScript template literal.
+    Template,
+    Comment,
+    /// In the body of a shell here document, which expands variables unless its delimiter is
+    /// quoted.
+    HereDocument {
+        expands: bool,
+    },
+}
+
+/// How the text at `index` of a script is quoted. `text` should have its expressions replaced by
+/// [`placeholders`], so that quotes inside them aren't read. Command substitutions, template
+/// literal substitutions and regular expressions aren't understood.
+pub(crate) fn quoting(language: Language, text: &str, index: usize) -> Quoting {
+    let bytes = text.as_bytes();
+    let mut state = Quoting::Unquoted;
+    // The here documents whose bodies start on the next line, and whether they expand variables.
+    let mut here_documents: Vec<(String, bool)> = Vec::new();
+    let mut position = 0;
+
+    while position < index {
+        let next = bytes.get(position + 1).copied();
+        let escapes = matches!(language, Language::Shell | Language::JavaScript);
+        match (state, bytes[position]) {
+            (Quoting::Single, b'\'') | (Quoting::Double, b'"') | (Quoting::Template, b'`') => {
+                state = Quoting::Unquoted;
+            }
+            (Quoting::Single, b'\\') if language == Language::JavaScript => position += 1,
+            (Quoting::Double | Quoting::Template | Quoting::Unquoted, b'\\') if escapes => {
+                position += 1;
+            }
+            (Quoting::Double | Quoting::Unquoted, b'`') if language == Language::PowerShell => {
+                position += 1;
+            }
+            (Quoting::Unquoted, b'\'') if language != Language::Cmd => state = Quoting::Single,
+            (Quoting::Unquoted, b'"') if language != Language::Cmd => state = Quoting::Double,
+            (Quoting::Unquoted, b'`') if language == Language::JavaScript => {
+                state = Quoting::Template;
+            }
+            (Quoting::Unquoted, b'#')
+                if matches!(language, Language::Shell | Language::PowerShell)
+                    && (position == 0 || bytes[position - 1].is_ascii_whitespace()) =>
+            {
+                let end = line_end(text, position);
+                if index < end {
+                    return Quoting::Comment;
+                }
+                position = end;
+                continue;
+            }
+            (Quoting::Unquoted, b'/')
+                if language == Language::JavaScript && matches!(next, Some(b'/' | b'*')) =>
+            {
+                let end = if next == Some(b'/') {
+                    line_end(text, position)
+                } else {
+                    text[position + 2..]
+                        .find("*/")
+                        .map_or(text.len(), |end| position + 2 + end + 2)
+                };
+                if index < end {
+                    return Quoting::Comment;
+                }
+                position = end;
+                continue;
+            }
+            (Quoting::Unquoted, b'<')
+                if language == Language::Shell
+                    && next == Some(b'<')
+                    && bytes.get(position + 2) != Some(&b'<') =>
+            {
+                let (delimiter, end) = here_document(text, position);
+                let expands = !delimiter.contains(['\'', '"', '\\']);
+                here_documents.push((delimiter.replace(['\'', '"', '\\'], ""), expands));
+                position = end;
+                continue;
+            }
+            (Quoting::Unquoted, b'\n') if language == Language::Shell => {
+                position += 1;
+                for (delimiter, expands) in here_documents.drain(..) {
+                    let end = here_document_end(text, position, &delimiter);
+                    if index < end {
+                        return Quoting::HereDocument { expands };
+                    }
+                    position = end;
+                }
+                continue;
+            }
+            _ => {}
+        }
+        position += 1;
+    }
+
+    state
+}
+
+/// The offset of the end of the line that `index` is on.
+fn line_end(text: &str, index: usize) -> usize {
+    text[index..]
+        .find('\n')
+        .map_or(text.len(), |end| index + end)
+}
+
+/// The delimiter word of the here document whose `<<` operator is at `index`, with its quotes,
+/// and the offset after it.
+pub(crate) fn here_document(text: &str, index: usize) -> (&str, usize) {
+    let start = index + 2 + usize::from(text.as_bytes().get(index + 2) == Some(&b'-'));
+    let start = text.len() - text[start..].trim_start_matches([' ', '\t']).len();
+    let end = text[start..]
+        .find(|c: char| c.is_whitespace() || ";|&()<>".contains(c))
+        .map_or(text.len(), |end| start + end);
+    (&text[start..end], end)
+}
+
+/// The offset after the line that ends a here document whose body starts at `start`. The lines
+/// are trimmed, since they are indented in the workflow.
+pub(crate) fn here_document_end(text: &str, start: usize, delimiter: &str) -> usize {
+    let mut end = start;
+    for line in text[start..].split_inclusive('\n') {
+        end += line.len();
+        if line.trim() == delimiter {
+            return end;
+        }
+    }
+    end
+}
+
+/// The script with each `${{ }}` expression replaced by a placeholder of the same length, so
+/// that it can be read as a shell script and offsets still match the source.
+pub(crate) fn placeholders(script: &str) -> String {
+    let mut text = script.to_string();
+    for (range, _) in expressions(script) {
+        let length = range.len();
+        text.replace_range(range, &"_".repeat(length));
+    }
+    text
+}
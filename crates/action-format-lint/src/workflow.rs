@@ -0,0 +1,134 @@
+use action_format_core::{Action, FileKind, Job, Node, Spanned, Step, Workflow};
+
+/// The key and value of the first entry with the given key, if `node` is a mapping.
+pub(crate) fn entry<'a>(node: &'a Node, key: &str) -> Option<(&'a Node, &'a Node)> {
+    node.as_mapping()?
+        .iter()
+        .find(|(k, _)| k.as_str() == Some(key))
+        .map(|(key, value)| (key, value))
+}
+
+/// The model of a workflow or composite action, which the rules walk instead of the document.
+pub(crate) enum Model {
+    Workflow(Workflow),
+    Action(Action),
+    /// Files without jobs or steps, such as dependabot configuration.
+    Other,
+}
+
+impl Model {
+    pub(crate) fn new(document: &Node, kind: FileKind) -> Self {
+        match kind {
+            FileKind::Workflow => Self::Workflow(Workflow::from_node(document)),
+            FileKind::Action => Self::Action(Action::from_node(document)),
+            _ => Self::Other,
+        }
+    }
+
+    /// The workflow, if the file is one.
+    pub(crate) fn workflow(&self) -> Option<&Workflow> {
+        match self {
+            Self::Workflow(workflow) => Some(workflow),
+            _ => None,
+        }
+    }
+
+    /// The jobs of a workflow.
+    pub(crate) fn jobs(&self) -> &[Job] {
+        self.workflow().map_or(&[], |workflow| &workflow.jobs)
+    }
+
+    /// The steps of every job of a workflow, or of a composite action.
+    pub(crate) fn steps(&self) -> Vec<&Step> {
+        match self {
+            Self::Workflow(workflow) => workflow.steps().map(|(_, step)| step).collect(),
+            Self::Action(action) => action.steps.iter().collect(),
+            Self::Other => Vec::new(),
+        }
+    }
+
+    /// The `uses` values of steps and of jobs calling reusable workflows, in source order.
+    pub(crate) fn uses(&self) -> Vec<&Spanned<String>> {
+        let mut uses: Vec<&Spanned<String>> = self
+            .jobs()
+            .iter()
+            .filter_map(|job| job.uses.as_ref())
+            .chain(
+                self.steps()
+                    .into_iter()
+                    .filter_map(|step| step.uses.as_ref()),
+            )
+            .collect();
+        uses.sort_by_key(|uses| uses.span);
+        uses
+    }
+
+    /// The names of the events that trigger a workflow.
+    pub(crate) fn triggers(&self) -> Vec<&str> {
+        self.workflow()
+            .map(|workflow| {
+                workflow
+                    .triggers
+                    .iter()
+                    .map(|trigger| trigger.event.as_str())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The `cron` values of the `schedule` trigger of a workflow.
+    pub(crate) fn crons(&self) -> &[Spanned<String>] {
+        self.workflow()
+            .and_then(|workflow| workflow.trigger("schedule"))
+            .map_or(&[], |schedule| &schedule.cron)
+    }
+}
+
+/// Whether a key names an input or secret, which GitHub compares case-insensitively.
+pub(crate) fn is_named(key: &Node, name: &str) -> bool {
+    key.as_str()
+        .is_some_and(|key| key.eq_ignore_ascii_case(name))
+}
+
+/// A reference to a remote action or reusable workflow, such as `actions/cache/save@v4`.
+pub(crate) struct ActionRef<'a> {
+    /// The action path before the `@`, such as `actions/cache/save`.
+    pub action: &'a str,
+    /// The repository containing the action, such as `actions/cache`.
+    pub repository: &'a str,
+    /// The tag, branch or commit SHA after the `@`.
+    pub reference: &'a str,
+}
+
+impl<'a> ActionRef<'a> {
+    /// Parse a `uses` value, returning `None` for local actions and Docker images.
+    pub(crate) fn parse(value: &'a str) -> Option<Self> {
+        if value.starts_with("./") || value.starts_with("docker://") {
+            return None;
+        }
+
+        let (action, reference) = value.split_once('@')?;
+        let repository = match action.match_indices('/').nth(1) {
+            Some((index, _)) => &action[..index],
+            None => action,
+        };
+
+        Some(Self {
+            action,
+            repository,
+            reference,
+        })
+    }
+
+    /// Whether the reference is a 40 character hexadecimal commit SHA.
+    pub(crate) fn is_pinned(&self) -> bool {
+        self.reference.len() == 40 && self.reference.bytes().all(|byte| byte.is_ascii_hexdigit())
+    }
+}
+
+/// The version of the `# v1.2.3` comment at the end of a line, which is its first word, since
+/// the comment can go on after it.
+pub(crate) fn version_comment(line: &str) -> Option<&str> {
+    let (_, comment) = line.split_once(" #")?;
+    comment.split_whitespace().next()
+}
@@ -0,0 +1,154 @@
+use std::path::Path;
+use std::process::{Command, Output};
+
+use anyhow::{Context as _, Result};
+use serde::Deserialize;
+
+use action_format_cli::ExternalTool;
+use action_format_core::{FileKind, Span};
+use action_format_lint::{Severity, external_scripts};
+
+/// A problem reported by an external tool, at its position in the linted file.
+pub(crate) struct ExternalDiagnostic {
+    pub(crate) code: String,
+    pub(crate) severity: Severity,
+    pub(crate) span: Span,
+    pub(crate) message: String,
+}
+
+/// The name of the program of a tool.
+fn program(tool: ExternalTool) -> &'static str {
+    match tool {
+        ExternalTool::Shellcheck => "shellcheck",
+        ExternalTool::Actionlint => "actionlint",
+    }
+}
+
+/// Check that a tool is installed, by running it with `--version`.
+pub(crate) fn find(tool: ExternalTool) -> Result<()> {
+    let program = program(tool);
+    Command::new(program)
+        .arg("--version")
+        .output()
+        .with_context(|| format!("Failed to run `{program}`, is it installed?"))?;
+    Ok(())
+}
+
+/// Run an external tool on a file, returning its problems.
+pub(crate) fn check(
+    tool: ExternalTool,
+    path: &Path,
+    content: &str,
+    kind: FileKind,
+) -> Result<Vec<ExternalDiagnostic>> {
+    match tool {
+        ExternalTool::Shellcheck => shellcheck(content, kind),
+        ExternalTool::Actionlint => actionlint(path, kind),
+    }
+}
+
+/// The output of `shellcheck --format=json1`.
+#[derive(Deserialize)]
+struct ShellcheckOutput {
+    comments: Vec<ShellcheckComment>,
+}
+
+#[derive(Deserialize)]
+struct ShellcheckComment {
+    line: usize,
+    column: usize,
+    level: String,
+    code: u32,
+    message: String,
+}
+
+/// Check each `run` script with shellcheck, by writing it to a temporary file.
+fn shellcheck(content: &str, kind: FileKind) -> Result<Vec<ExternalDiagnostic>> {
+    let mut diagnostics = Vec::new();
+
+    for script in external_scripts(content, kind)? {
+        let file = tempfile::Builder::new()
+            .prefix("action-format-")
+            .suffix(".sh")
+            .tempfile()?;
+        fs_err::write(file.path(), &script.text)?;
+
+        let output = run(Command::new(program(ExternalTool::Shellcheck))
+            .arg("--format=json1")
+            .arg(format!("--shell={}", script.shell))
+            .arg(file.path()))?;
+        let output: ShellcheckOutput = serde_json::from_slice(&output.stdout)
+            .context("Failed to read the output of `shellcheck`")?;
+
+        diagnostics.extend(
+            output
+                .comments
+                .into_iter()
+                .map(|comment| ExternalDiagnostic {
+                    code: format!("SC{}", comment.code),
+                    severity: if comment.level == "error" {
+                        Severity::Error
+                    } else {
+                        Severity::Warning
+                    },
+                    span: script.span(comment.line, comment.column),
+                    message: comment.message,
+                }),
+        );
+    }
+
+    Ok(diagnostics)
+}
+
+/// An error in the output of `actionlint -format '{{json .}}'`.
+#[derive(Deserialize)]
+struct ActionlintError {
+    message: String,
+    line: usize,
+    column: usize,
+    kind: String,
+}
+
+/// Check a workflow with actionlint.
+fn actionlint(path: &Path, kind: FileKind) -> Result<Vec<ExternalDiagnostic>> {
+    if kind != FileKind::Workflow {
+        return Ok(Vec::new());
+    }
+
+    let output = run(Command::new(program(ExternalTool::Actionlint))
+        .args(["-format", "{{json .}}"])
+        .arg(path))?;
+    let errors: Vec<ActionlintError> = serde_json::from_slice(&output.stdout)
+        .context("Failed to read the output of `actionlint`")?;
+
+    Ok(errors
+        .into_iter()
+        .map(|error| ExternalDiagnostic {
+            code: format!("actionlint/{}", error.kind),
+            severity: Severity::Error,
+            span: Span {
+                line: error.line,
+                column: error.column,
+            },
+            message: error.message,
+        })
+        .collect())
+}
+
+/// Run a tool, which exits with 0 without problems and 1 with problems.
+fn run(command: &mut Command) -> Result<Output> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to run `{program}`, is it installed?"))?;
+
+    if !matches!(output.status.code(), Some(0 | 1)) {
+        anyhow::bail!(
+            "`{program}` failed with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(output)
+}
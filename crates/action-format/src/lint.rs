@@ -0,0 +1,224 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+
+use action_format_cli::{ExternalTool, LintArgs};
+use action_format_core::{FormatterConfig, detect_kind};
+use action_format_lint::{
+    Diagnostic, LintConfig, Note, Project, Severity, explain_string, fix_string, lint_string,
+};
+
+use crate::external::{self, ExternalDiagnostic};
+use crate::printer::Printer;
+use crate::{ExitStatus, discover_files};
+
+/// Lint the discovered files, printing a line for each diagnostic, for each problem found by the
+/// tools of `--external`, and for each note with `--explain`.
+pub(crate) fn lint(
+    config_path: &Path,
+    config: &FormatterConfig,
+    args: &LintArgs,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let lint_config = LintConfig::from_file(config_path)?;
+
+    let mut problems = 0;
+    let mut fixable = 0;
+    let mut fixed = 0;
+    let mut any_failure = false;
+    let mut any_error = false;
+
+    let mut tools = Vec::new();
+    for &tool in &args.external {
+        match external::find(tool) {
+            Ok(()) => tools.push(tool),
+            Err(e) => {
+                let _ = writeln!(printer.stderr(), "{}: {e}", "error".red().bold());
+                any_error = true;
+            }
+        }
+    }
+
+    let paths = discover_files(config);
+    let project = Project::load(Path::new("."), &paths);
+
+    for path in paths {
+        let result = lint_file(&path, &lint_config, &project, args, &tools);
+
+        match result {
+            Ok(report) => {
+                fixed += report.fixed;
+                fixable += report
+                    .diagnostics
+                    .iter()
+                    .filter(|diagnostic| diagnostic.fix.is_some())
+                    .count();
+                let mut lines = Vec::new();
+                let external = report.external.into_iter().map(|diagnostic| {
+                    (
+                        diagnostic.span,
+                        diagnostic.severity,
+                        diagnostic.code,
+                        diagnostic.message,
+                    )
+                });
+                for (span, severity, code, message) in report
+                    .diagnostics
+                    .into_iter()
+                    .map(|diagnostic| {
+                        (
+                            diagnostic.span,
+                            diagnostic.severity,
+                            diagnostic.code.to_string(),
+                            diagnostic.message,
+                        )
+                    })
+                    .chain(external)
+                {
+                    let label = format!("{severity}[{code}]");
+                    let label = match severity {
+                        Severity::Error => {
+                            any_failure = true;
+                            label.red().bold().to_string()
+                        }
+                        Severity::Warning => label.yellow().bold().to_string(),
+                    };
+                    lines.push((span, format!("{label} {message}")));
+                    problems += 1;
+                }
+                for note in report.notes {
+                    lines.push((
+                        note.span,
+                        format!("{} {}", "note:".cyan().bold(), note.message),
+                    ));
+                }
+
+                lines.sort_by_key(|(span, _)| (span.line, span.column));
+                for (span, line) in lines {
+                    let _ = writeln!(
+                        printer.stdout(),
+                        "{}:{}:{}: {line}",
+                        path.display(),
+                        span.line,
+                        span.column
+                    );
+                }
+                for e in report.external_errors {
+                    let _ = writeln!(
+                        printer.stderr(),
+                        "{}: {}: {e}",
+                        "error".red().bold(),
+                        path.display(),
+                    );
+                    any_error = true;
+                }
+            }
+            Err(e) => {
+                let _ = writeln!(
+                    printer.stderr(),
+                    "{}: {}: {}",
+                    "error".red().bold(),
+                    path.display(),
+                    e
+                );
+                any_error = true;
+            }
+        }
+    }
+
+    if fixed > 0 {
+        let _ = writeln!(
+            printer.stdout(),
+            "Fixed {fixed} {}",
+            plural(fixed, "problem")
+        );
+    }
+    if problems > 0 {
+        let _ = write!(
+            printer.stdout(),
+            "Found {problems} {}",
+            plural(problems, "problem")
+        );
+        if fixable > 0 {
+            let _ = write!(printer.stdout(), " ({fixable} fixable with `--fix`)");
+        }
+        let _ = writeln!(printer.stdout());
+    }
+
+    if any_error {
+        Ok(ExitStatus::Error)
+    } else if any_failure {
+        Ok(ExitStatus::Failure)
+    } else {
+        Ok(ExitStatus::Success)
+    }
+}
+
+/// The problems and notes of a file.
+struct Report {
+    diagnostics: Vec<Diagnostic>,
+    /// The problems found by the tools of `--external`.
+    external: Vec<ExternalDiagnostic>,
+    /// The errors of the tools of `--external` that failed on the file.
+    external_errors: Vec<anyhow::Error>,
+    /// The notes of `--explain`.
+    notes: Vec<Note>,
+    /// The number of problems fixed by `--fix`.
+    fixed: usize,
+}
+
+/// Lint a file, first applying and writing the fixes with `--fix`, and report the remaining
+/// problems, including those found by the installed external `tools`.
+fn lint_file(
+    path: &Path,
+    config: &LintConfig,
+    project: &Project,
+    args: &LintArgs,
+    tools: &[ExternalTool],
+) -> Result<Report> {
+    let mut content = fs_err::read_to_string(path)?;
+    let kind = detect_kind(path, &content);
+
+    let mut fixed = 0;
+    if args.fix {
+        let (fixed_content, count) = fix_string(path, &content, kind, config, project)?;
+        if count > 0 {
+            fs_err::write(path, &fixed_content)?;
+            content = fixed_content;
+            fixed = count;
+        }
+    }
+
+    let diagnostics = lint_string(path, &content, kind, config, project)?;
+    let mut external = Vec::new();
+    let mut external_errors = Vec::new();
+    for &tool in tools {
+        match external::check(tool, path, &content, kind) {
+            Ok(diagnostics) => external.extend(diagnostics),
+            Err(e) => external_errors.push(e),
+        }
+    }
+    let notes = if args.explain {
+        explain_string(&content, kind)?
+    } else {
+        Vec::new()
+    };
+
+    Ok(Report {
+        diagnostics,
+        external,
+        external_errors,
+        notes,
+        fixed,
+    })
+}
+
+fn plural(count: usize, word: &str) -> String {
+    if count == 1 {
+        word.to_string()
+    } else {
+        format!("{word}s")
+    }
+}
@@ -1,5 +1,5 @@
 use std::fmt::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
 use anyhow::Result;
@@ -7,13 +7,18 @@ use clap::Parser;
 use owo_colors::{OwoColorize, Style};
 use similar::{Algorithm, ChangeTag, TextDiff};
 
-use action_format_cli::Cli;
-use action_format_core::{FormatError, FormatterConfig, format_string};
+use action_format_cli::{Cli, Command};
+use action_format_core::{
+    FileKind, FormatError, FormatterConfig, detect_kind, format_string_with_kind, verify_format,
+};
 
+mod external;
+mod lint;
+mod pin;
 mod printer;
 use printer::Printer;
 
-const WORKFLOWS_DIR: &str = ".github/workflows";
+const GITHUB_DIR: &str = ".github";
 const CONFIG_FILE: &str = ".github/action-format.toml";
 
 #[derive(Copy, Clone)]
@@ -66,34 +71,34 @@ fn main() -> ExitCode {
 }
 
 fn run(cli: &Cli, printer: Printer) -> Result<ExitStatus> {
-    let workflows_path = Path::new(WORKFLOWS_DIR);
-
-    if !workflows_path.exists() {
-        anyhow::bail!("No {WORKFLOWS_DIR} directory found");
+    if !Path::new(GITHUB_DIR).exists() {
+        anyhow::bail!("No {GITHUB_DIR} directory found");
     }
 
     let config_path = Path::new(CONFIG_FILE);
     let config = FormatterConfig::from_file(config_path)?;
 
+    match cli.command {
+        Some(Command::Lint(ref args)) => lint::lint(config_path, &config, args, printer),
+        Some(Command::Pin(ref args)) => pin::pin(config_path, &config, args, printer),
+        None => Ok(format_files(cli, &config, printer)),
+    }
+}
+
+/// Format the discovered files.
+fn format_files(cli: &Cli, config: &FormatterConfig, printer: Printer) -> ExitStatus {
     let mut any_changed = false;
     let mut any_error = false;
 
-    let walker = walkdir::WalkDir::new(workflows_path)
-        .sort_by_file_name()
-        .into_iter()
-        .filter_map(Result::ok)
-        .filter(|e| is_workflow_file(e.path()))
-        .filter(|e| !config.should_ignore(e.path()));
-
-    for entry in walker {
-        match process_file(entry.path(), &config, cli, printer) {
+    for path in discover_files(config) {
+        match process_file(&path, config, cli, printer) {
             Ok(changed) => any_changed |= changed,
             Err(e) => {
                 let _ = writeln!(
                     printer.stderr(),
                     "{}: {}: {}",
                     "error".red().bold(),
-                    entry.path().display(),
+                    path.display(),
                     e
                 );
                 any_error = true;
@@ -102,19 +107,25 @@ fn run(cli: &Cli, printer: Printer) -> Result<ExitStatus> {
     }
 
     if any_error {
-        Ok(ExitStatus::Error)
+        ExitStatus::Error
     } else if cli.check && any_changed {
-        Ok(ExitStatus::Failure)
+        ExitStatus::Failure
     } else {
-        Ok(ExitStatus::Success)
+        ExitStatus::Success
     }
 }
 
-fn is_workflow_file(path: &Path) -> bool {
-    path.is_file()
-        && path
-            .extension()
-            .is_some_and(|ext| ext == "yml" || ext == "yaml")
+/// Find the YAML files in `.github` whose kind is included by the configuration.
+fn discover_files(config: &FormatterConfig) -> Vec<PathBuf> {
+    walkdir::WalkDir::new(GITHUB_DIR)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .map(walkdir::DirEntry::into_path)
+        .filter(|path| FileKind::from_path(path).is_some_and(|kind| config.include.contains(&kind)))
+        .filter(|path| !config.should_ignore(path))
+        .collect()
 }
 
 fn process_file(
@@ -124,7 +135,12 @@ fn process_file(
     printer: Printer,
 ) -> Result<bool, FormatError> {
     let content = fs_err::read_to_string(path)?;
-    let formatted = format_string(&content, config)?;
+    let kind = detect_kind(path, &content);
+    let formatted = format_string_with_kind(&content, config, kind)?;
+
+    if cli.verify {
+        verify_format(&content, &formatted, config, kind)?;
+    }
 
     if content == formatted {
         return Ok(false);
@@ -0,0 +1,98 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+
+use action_format_cli::PinArgs;
+use action_format_core::{FormatterConfig, detect_kind};
+use action_format_lint::{ActionsLock, LintConfig, pin_string};
+
+use crate::printer::Printer;
+use crate::{ExitStatus, discover_files};
+
+const LOCK_FILE: &str = ".github/actions.lock";
+
+/// Pin the actions of the discovered files to the commit SHAs in the lock file.
+pub(crate) fn pin(
+    config_path: &Path,
+    config: &FormatterConfig,
+    args: &PinArgs,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let lock_path = Path::new(LOCK_FILE);
+    if !lock_path.exists() {
+        anyhow::bail!("No {LOCK_FILE} file found");
+    }
+
+    let lock = ActionsLock::from_file(lock_path)?;
+    let lint_config = LintConfig::from_file(config_path)?;
+
+    let mut any_changed = false;
+    let mut any_missing = false;
+    let mut any_error = false;
+
+    for path in discover_files(config) {
+        let result = fs_err::read_to_string(&path)
+            .map_err(Into::into)
+            .and_then(|content| {
+                let kind = detect_kind(&path, &content);
+                pin_string(&content, kind, &lock, &lint_config).map(|pinned| (content, pinned))
+            });
+
+        let (content, pinned) = match result {
+            Ok(result) => result,
+            Err(e) => {
+                let _ = writeln!(
+                    printer.stderr(),
+                    "{}: {}: {}",
+                    "error".red().bold(),
+                    path.display(),
+                    e
+                );
+                any_error = true;
+                continue;
+            }
+        };
+
+        for (span, action) in &pinned.missing {
+            let _ = writeln!(
+                printer.stdout(),
+                "{}:{}:{}: `{action}` is missing from {LOCK_FILE}",
+                path.display(),
+                span.line,
+                span.column,
+            );
+            any_missing = true;
+        }
+
+        if pinned.content == content {
+            continue;
+        }
+        any_changed = true;
+
+        if args.check {
+            let _ = writeln!(
+                printer.stdout(),
+                "{}: {}",
+                "Would pin".yellow(),
+                path.display()
+            );
+        } else {
+            if let Err(e) = fs_err::write(&path, &pinned.content) {
+                let _ = writeln!(printer.stderr(), "{}: {}", "error".red().bold(), e);
+                any_error = true;
+                continue;
+            }
+            let _ = writeln!(printer.stdout(), "{}: {}", "Pinned".green(), path.display());
+        }
+    }
+
+    if any_error {
+        Ok(ExitStatus::Error)
+    } else if any_missing || (args.check && any_changed) {
+        Ok(ExitStatus::Failure)
+    } else {
+        Ok(ExitStatus::Success)
+    }
+}
@@ -76,6 +76,20 @@ impl TestContext {
         self
     }
 
+    /// Create an action metadata file at .github/actions/{name}/action.yml with the given content.
+    pub fn action(&self, name: &str, content: &str) -> &Self {
+        self.file(&format!(".github/actions/{name}/action.yml"), content)
+    }
+
+    /// Create a file at the given path relative to the root with the given content.
+    pub fn file(&self, path: &str, content: &str) -> &Self {
+        self.root
+            .child(path)
+            .write_str(content.strip_prefix('\n').unwrap_or(content))
+            .expect("Failed to write file");
+        self
+    }
+
     /// Create a config file at .github/action-format.toml with the given content.
     pub fn config(&self, content: &str) -> &Self {
         self.root
@@ -85,17 +99,47 @@ impl TestContext {
         self
     }
 
+    /// Create an executable at bin/{name} with the given content, which `command` finds on `PATH`
+    /// before any installed program.
+    #[cfg(unix)]
+    pub fn bin(&self, name: &str, content: &str) -> &Self {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = format!("bin/{name}");
+        self.file(&path, content);
+        std::fs::set_permissions(self.root.join(path), std::fs::Permissions::from_mode(0o755))
+            .expect("Failed to make file executable");
+        self
+    }
+
     /// Read a file from .github/workflows and return its contents.
     pub fn read_workflow(&self, name: &str) -> String {
         std::fs::read_to_string(self.root.join(format!(".github/workflows/{name}")))
             .unwrap_or_else(|_| panic!("Failed to read workflow file: {name}"))
     }
 
+    /// Read .github/actions/{name}/action.yml and return its contents.
+    pub fn read_action(&self, name: &str) -> String {
+        self.read_file(&format!(".github/actions/{name}/action.yml"))
+    }
+
+    /// Read a file relative to the root and return its contents.
+    pub fn read_file(&self, path: &str) -> String {
+        std::fs::read_to_string(self.root.join(path))
+            .unwrap_or_else(|_| panic!("Failed to read file: {path}"))
+    }
+
     /// Create an action-format command for testing.
     pub fn command(&self) -> Command {
         let mut command = Command::new(get_bin());
         command.current_dir(self.root.path());
         command.env("NO_COLOR", "1");
+        let path = std::env::var_os("PATH").unwrap_or_default();
+        let paths = std::iter::once(self.root.join("bin")).chain(std::env::split_paths(&path));
+        command.env(
+            "PATH",
+            std::env::join_paths(paths).expect("Failed to build PATH"),
+        );
         command
     }
 }
@@ -1784,6 +1784,119 @@ jobs:
     ");
 }
 
+#[test]
+fn test_verify_mode() {
+    let context = TestContext::new();
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+    build:
+        runs-on: ubuntu-latest
+        steps:
+            - uses: actions/checkout@v4
+            - name: Build
+              run: |
+                  cargo build
+                      --release
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("--verify"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Reformatted: .github/workflows/ci.yml
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    name: CI
+    on: push
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        steps:
+          - uses: actions/checkout@v4
+
+          - name: Build
+            run: |
+              cargo build
+                  --release
+    ");
+}
+
+#[test]
+fn test_verify_mode_invalid_yaml() {
+    let context = TestContext::new();
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build: [
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("--verify"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: .github/workflows/ci.yml: Invalid YAML at line 5, column 1: while parsing a node, did not find expected node content
+    ");
+
+    // Original file left unchanged
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    name: CI
+    on: push
+    jobs:
+      build: [
+    ");
+}
+
+#[test]
+fn test_format_keep_chomping_scalar_not_separated() {
+    let context = TestContext::new();
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - name: Notes
+        run: |+
+          echo notes
+      - name: Build
+        run: cargo build
+",
+    );
+
+    context.command().arg("--verify").assert().success();
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    name: CI
+    on: push
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        steps:
+          - name: Notes
+            run: |+
+              echo notes
+          - name: Build
+            run: cargo build
+    ");
+}
+
 // Config file tests
 
 #[test]
@@ -1821,7 +1934,7 @@ jobs:
                 - uses: actions/checkout@v4
 
                 - name: Build
-                    run: cargo build
+                  run: cargo build
     ");
 }
 
@@ -2149,3 +2262,1182 @@ jobs:
 
     context.command().assert().failure();
 }
+
+#[test]
+fn test_config_sequence_indent_indented() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+sequence_indent = "indented"
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on:
+  push:
+    branches:
+    - main
+jobs:
+  test:
+    strategy:
+      matrix:
+        include:
+        - os: ubuntu-latest
+          node: 18
+        - os: windows-latest
+          node: 20
+    steps:
+    - uses: actions/checkout@v4
+      with:
+        args:
+        - a
+        - b
+",
+    );
+
+    context.command().assert().success();
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    name: CI
+    on:
+      push:
+        branches:
+          - main
+    jobs:
+      test:
+        strategy:
+          matrix:
+            include:
+              - os: ubuntu-latest
+                node: 18
+              - os: windows-latest
+                node: 20
+        steps:
+          - uses: actions/checkout@v4
+            with:
+              args:
+                - a
+                - b
+    ");
+}
+
+#[test]
+fn test_config_sequence_indent_flush() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+sequence_indent = "flush"
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on:
+  push:
+    branches:
+      - main
+jobs:
+  test:
+    strategy:
+      matrix:
+        include:
+          - os: ubuntu-latest
+            node: 18
+          - os: windows-latest
+            node: 20
+    steps:
+      # Checkout the code
+      - uses: actions/checkout@v4
+        with:
+          args:
+            - a
+            - b
+      - name: Build
+        run: |
+          cargo build
+",
+    );
+
+    context.command().assert().success();
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    name: CI
+    on:
+      push:
+        branches:
+        - main
+    jobs:
+      test:
+        strategy:
+          matrix:
+            include:
+            - os: ubuntu-latest
+              node: 18
+            - os: windows-latest
+              node: 20
+        steps:
+        # Checkout the code
+        - uses: actions/checkout@v4
+          with:
+            args:
+            - a
+            - b
+
+        - name: Build
+          run: |
+            cargo build
+    ");
+}
+
+#[test]
+fn test_config_sequence_indent_preserve() {
+    let context = TestContext::new();
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on:
+    push:
+        branches:
+        - main
+jobs:
+    test:
+        steps:
+            -   uses: actions/checkout@v4
+                with:
+                    args:
+                    - a
+",
+    );
+
+    context.command().assert().success();
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    name: CI
+    on:
+      push:
+        branches:
+        - main
+    jobs:
+      test:
+        steps:
+          - uses: actions/checkout@v4
+            with:
+              args:
+              - a
+    ");
+}
+
+#[test]
+fn test_config_sequence_indent_invalid() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+sequence_indent = "sideways"
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+",
+    );
+
+    context.command().assert().failure();
+}
+
+#[test]
+fn test_format_block_scalar_keeps_relative_indent() {
+    let context = TestContext::new();
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+    build:
+        runs-on: ubuntu-latest
+        steps:
+            -   name: Build
+                run: |
+                    if [ -f Cargo.toml ]; then
+                        cargo build
+                    fi
+",
+    );
+
+    context.command().assert().success();
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    name: CI
+    on: push
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        steps:
+          - name: Build
+            run: |
+              if [ -f Cargo.toml ]; then
+                  cargo build
+              fi
+    ");
+}
+
+#[test]
+fn test_config_separate_top_level() {
+    let context = TestContext::new();
+    context.config(
+        r"
+separate_top_level = true
+",
+    );
+    context.workflow(
+        "ci.yml",
+        r"# CI workflow
+name: CI
+on:
+  push:
+    branches: [main]
+permissions:
+  contents: read
+env:
+  CARGO_TERM_COLOR: always
+
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+",
+    );
+
+    context.command().assert().success();
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    # CI workflow
+    name: CI
+
+    on:
+      push:
+        branches: [main]
+
+    permissions:
+      contents: read
+
+    env:
+      CARGO_TERM_COLOR: always
+
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        steps:
+          - uses: actions/checkout@v4
+    ");
+}
+
+#[test]
+fn test_config_separate_top_level_multiple_documents() {
+    let context = TestContext::new();
+    context.config(
+        r"
+separate_top_level = true
+",
+    );
+    context.workflow(
+        "ci.yml",
+        r"---
+name: CI
+on: push
+...
+---
+# Second document
+name: Release
+on: push
+---
+name: Docs
+",
+    );
+
+    context.command().assert().success();
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    ---
+    name: CI
+
+    on: push
+    ...
+    ---
+    # Second document
+    name: Release
+
+    on: push
+    ---
+    name: Docs
+    ");
+}
+
+#[test]
+fn test_config_remove_blank_lines() {
+    let context = TestContext::new();
+    context.config(
+        r"
+preserve_blank_lines = false
+",
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+
+on: push
+
+jobs:
+
+  build:
+    runs-on: ubuntu-latest
+
+    steps:
+      - name: Build
+
+        run: cargo build
+
+
+      - name: Test
+        run: |
+          cargo test
+
+          cargo test --doc
+
+  lint:
+    runs-on: ubuntu-latest
+",
+    );
+
+    context.command().assert().success();
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    name: CI
+    on: push
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        steps:
+          - name: Build
+            run: cargo build
+
+          - name: Test
+            run: |
+              cargo test
+
+              cargo test --doc
+
+      lint:
+        runs-on: ubuntu-latest
+    ");
+}
+
+#[test]
+fn test_config_remove_blank_lines_without_separation() {
+    let context = TestContext::new();
+    context.config(
+        r"
+preserve_blank_lines = false
+separate_steps = false
+separate_jobs = false
+",
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+
+      - run: cargo build
+
+  lint:
+    runs-on: ubuntu-latest
+",
+    );
+
+    context.command().assert().success();
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    name: CI
+    on: push
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        steps:
+          - uses: actions/checkout@v4
+          - run: cargo build
+      lint:
+        runs-on: ubuntu-latest
+    ");
+}
+
+#[test]
+fn test_config_remove_blank_lines_keeps_multi_line_scalars() {
+    let context = TestContext::new();
+    context.config(
+        r"
+preserve_blank_lines = false
+",
+    );
+    context.workflow(
+        "ci.yml",
+        r#"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+
+    steps:
+      - run: "echo a
+
+          b"
+
+      - run: 'echo c
+
+
+          d'
+      - run: echo e
+
+          f
+
+      - name: Done
+"#,
+    );
+
+    context.command().assert().success();
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r#"
+    name: CI
+    on: push
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        steps:
+          - run: "echo a
+
+              b"
+
+          - run: 'echo c
+
+
+              d'
+
+          - run: echo e
+
+              f
+
+          - name: Done
+    "#);
+}
+
+#[test]
+fn test_config_separate_collections() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+separate = ["jobs", "include", "services", "inputs", "outputs"]
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on:
+  workflow_dispatch:
+    inputs:
+      environment:
+        type: string
+      debug:
+        type: boolean
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    outputs:
+      version: ${{ steps.version.outputs.version }}
+      sha: ${{ github.sha }}
+    services:
+      postgres:
+        image: postgres:16
+      redis:
+        image: redis:7
+    strategy:
+      matrix:
+        include:
+          - os: ubuntu-latest
+            node: 18
+          - os: windows-latest
+            node: 20
+    steps:
+      - uses: actions/checkout@v4
+      - run: cargo build
+  test:
+    runs-on: ubuntu-latest
+",
+    );
+
+    context.command().assert().success();
+
+    // Steps are no longer listed, so they are not separated
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    name: CI
+    on:
+      workflow_dispatch:
+        inputs:
+          environment:
+            type: string
+
+          debug:
+            type: boolean
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        outputs:
+          version: ${{ steps.version.outputs.version }}
+
+          sha: ${{ github.sha }}
+        services:
+          postgres:
+            image: postgres:16
+
+          redis:
+            image: redis:7
+        strategy:
+          matrix:
+            include:
+              - os: ubuntu-latest
+                node: 18
+
+              - os: windows-latest
+                node: 20
+        steps:
+          - uses: actions/checkout@v4
+          - run: cargo build
+
+      test:
+        runs-on: ubuntu-latest
+    ");
+}
+
+#[test]
+fn test_config_separate_unknown_collection() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+separate = ["steps", "widgets"]
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+",
+    );
+
+    context.command().assert().failure();
+}
+
+#[test]
+fn test_format_steps_key_in_with_block() {
+    let context = TestContext::new();
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: some/pipeline-action@v1
+        with:
+          steps:
+            - lint
+            - test
+          jobs:
+            fast: true
+            slow: false
+      - run: cargo build
+",
+    );
+
+    context.command().assert().success();
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    name: CI
+    on: push
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        steps:
+          - uses: some/pipeline-action@v1
+            with:
+              steps:
+                - lint
+                - test
+              jobs:
+                fast: true
+                slow: false
+
+          - run: cargo build
+    ");
+}
+
+#[test]
+fn test_format_steps_text_in_run_script() {
+    let context = TestContext::new();
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - name: Write pipeline
+        run: |
+          cat > pipeline.yml <<EOF
+          steps:
+          - lint
+          - test
+          EOF
+      - run: cargo build
+",
+    );
+
+    context.command().assert().success();
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    name: CI
+    on: push
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        steps:
+          - name: Write pipeline
+            run: |
+              cat > pipeline.yml <<EOF
+              steps:
+              - lint
+              - test
+              EOF
+
+          - run: cargo build
+    ");
+}
+
+#[test]
+fn test_format_steps_key_outside_jobs() {
+    let context = TestContext::new();
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+env:
+  steps:
+    - one
+    - two
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo one
+      - run: echo two
+",
+    );
+
+    context.command().assert().success();
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    name: CI
+    on: push
+    env:
+      steps:
+        - one
+        - two
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        steps:
+          - run: echo one
+
+          - run: echo two
+    ");
+}
+
+#[test]
+fn test_format_step_separation_flush_sequence() {
+    let context = TestContext::new();
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+    - uses: actions/checkout@v4
+    - run: cargo build
+    timeout-minutes: 10
+",
+    );
+
+    context.command().assert().success();
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    name: CI
+    on: push
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        steps:
+        - uses: actions/checkout@v4
+
+        - run: cargo build
+        timeout-minutes: 10
+    ");
+}
+
+// Composite action tests
+
+#[test]
+fn test_format_composite_action() {
+    let context = TestContext::new();
+    context.action(
+        "setup",
+        r"name: Setup
+description: Set up the toolchain
+inputs:
+    toolchain:
+        description: Rust toolchain
+        default: stable
+outputs:
+    version:
+        description: Installed version
+        value: ${{ steps.install.outputs.version }}
+runs:
+    using: composite
+    steps:
+        - uses: dtolnay/rust-toolchain@master
+          with:
+              toolchain: ${{ inputs.toolchain }}
+        - id: install
+          shell: bash
+          run: |
+              echo version=$(rustc --version) >> $GITHUB_OUTPUT
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Reformatted: .github/actions/setup/action.yml
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_action("setup");
+    insta::assert_snapshot!(content, @r"
+    name: Setup
+    description: Set up the toolchain
+    inputs:
+      toolchain:
+        description: Rust toolchain
+        default: stable
+    outputs:
+      version:
+        description: Installed version
+        value: ${{ steps.install.outputs.version }}
+    runs:
+      using: composite
+      steps:
+        - uses: dtolnay/rust-toolchain@master
+          with:
+            toolchain: ${{ inputs.toolchain }}
+
+        - id: install
+          shell: bash
+          run: |
+            echo version=$(rustc --version) >> $GITHUB_OUTPUT
+    ");
+}
+
+#[test]
+fn test_format_composite_action_separate_inputs() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+separate = ["steps", "inputs", "outputs"]
+"#,
+    );
+    context.file(
+        ".github/actions/lint/action.yaml",
+        r"name: Lint
+inputs:
+  path:
+    default: .
+  fix:
+    default: 'false'
+runs:
+  using: composite
+  steps:
+    - run: cargo clippy
+      shell: bash
+",
+    );
+
+    context.command().assert().success();
+
+    let content = context.read_file(".github/actions/lint/action.yaml");
+    insta::assert_snapshot!(content, @r"
+    name: Lint
+    inputs:
+      path:
+        default: .
+
+      fix:
+        default: 'false'
+    runs:
+      using: composite
+      steps:
+        - run: cargo clippy
+          shell: bash
+    ");
+}
+
+#[test]
+fn test_format_actions_without_workflows_directory() {
+    let context = TestContext::new();
+    std::fs::remove_dir(context.root.join(".github/workflows"))
+        .expect("Failed to remove workflows directory");
+    context.action(
+        "setup",
+        r"name: Setup
+runs:
+  using: composite
+  steps:
+    - run: echo one
+      shell: bash
+    - run: echo two
+      shell: bash
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Reformatted: .github/actions/setup/action.yml
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_format_no_github_directory() {
+    let context = TestContext::new();
+    std::fs::remove_dir_all(context.root.join(".github"))
+        .expect("Failed to remove .github directory");
+
+    action_format_snapshot!(context.filters(), context.command(), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No .github directory found
+    ");
+}
+
+#[test]
+fn test_format_actions_directory_other_files_ignored() {
+    let context = TestContext::new();
+    let unformatted = r"name: Not an action
+list:
+    - a
+    - b
+";
+    context.file(".github/actions/setup/config.yml", unformatted);
+    context.action(
+        "setup",
+        r"name: Setup
+runs:
+    using: composite
+    steps:
+        - run: echo
+          shell: bash
+",
+    );
+    context.config(
+        r#"
+ignore = ["setup/action.yml"]
+"#,
+    );
+
+    action_format_snapshot!(context.filters(), context.command(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+
+    assert_eq!(
+        context.read_file(".github/actions/setup/config.yml"),
+        unformatted
+    );
+}
+
+// Other .github file tests
+
+#[test]
+fn test_format_other_github_files_not_included_by_default() {
+    let context = TestContext::new();
+    let dependabot = r"version: 2
+updates:
+    - package-ecosystem: cargo
+      directory: /
+";
+    context.file(".github/dependabot.yml", dependabot);
+
+    action_format_snapshot!(context.filters(), context.command(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+
+    assert_eq!(context.read_file(".github/dependabot.yml"), dependabot);
+}
+
+#[test]
+fn test_format_dependabot() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+include = ["workflow", "dependabot"]
+"#,
+    );
+    context.file(
+        ".github/dependabot.yml",
+        r"version: 2
+updates:
+    - package-ecosystem: cargo
+      directory: /
+      schedule:
+          interval: weekly
+    - package-ecosystem: github-actions
+      directory: /
+      schedule:
+          interval: weekly
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Reformatted: .github/dependabot.yml
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_file(".github/dependabot.yml");
+    insta::assert_snapshot!(content, @r"
+    version: 2
+    updates:
+      - package-ecosystem: cargo
+        directory: /
+        schedule:
+          interval: weekly
+
+      - package-ecosystem: github-actions
+        directory: /
+        schedule:
+          interval: weekly
+    ");
+}
+
+#[test]
+fn test_format_issue_forms_and_generic_yaml() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+include = ["issue-form", "yaml"]
+"#,
+    );
+    context.file(
+        ".github/ISSUE_TEMPLATE/bug.yml",
+        r"name: Bug report
+description: Report a bug
+body:
+    - type: textarea
+      attributes:
+          label: What happened?
+",
+    );
+    context.file(
+        ".github/labeler.yml",
+        r"docs:
+    - changed-files:
+        - any-glob-to-any-file: docs/**
+",
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+    build:
+        runs-on: ubuntu-latest
+",
+    );
+
+    // Workflows are no longer included
+    action_format_snapshot!(context.filters(), context.command(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Reformatted: .github/ISSUE_TEMPLATE/bug.yml
+    Reformatted: .github/labeler.yml
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_file(".github/ISSUE_TEMPLATE/bug.yml");
+    insta::assert_snapshot!(content, @r"
+    name: Bug report
+    description: Report a bug
+    body:
+      - type: textarea
+        attributes:
+          label: What happened?
+    ");
+
+    let content = context.read_file(".github/labeler.yml");
+    insta::assert_snapshot!(content, @r"
+    docs:
+      - changed-files:
+          - any-glob-to-any-file: docs/**
+    ");
+}
+
+#[test]
+fn test_format_generic_yaml_not_separated() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+include = ["yaml"]
+"#,
+    );
+    context.file(
+        ".github/release.yml",
+        r"jobs:
+    first:
+        title: First
+    second:
+        title: Second
+updates:
+    - one
+    - two
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Reformatted: .github/release.yml
+
+    ----- stderr -----
+    ");
+
+    // Workflow and dependabot collections only apply to those files
+    let content = context.read_file(".github/release.yml");
+    insta::assert_snapshot!(content, @r"
+    jobs:
+      first:
+        title: First
+      second:
+        title: Second
+    updates:
+      - one
+      - two
+    ");
+}
+
+#[test]
+fn test_format_workflow_template_detected_by_content() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+include = ["yaml"]
+"#,
+    );
+    context.file(
+        ".github/workflow-templates/ci.yml",
+        r"name: CI
+on: push
+jobs:
+    build:
+        runs-on: ubuntu-latest
+        steps:
+            - uses: actions/checkout@v4
+            - run: make
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Reformatted: .github/workflow-templates/ci.yml
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_file(".github/workflow-templates/ci.yml");
+    insta::assert_snapshot!(content, @r"
+    name: CI
+    on: push
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        steps:
+          - uses: actions/checkout@v4
+
+          - run: make
+    ");
+}
+
+#[test]
+fn test_config_include_unknown_kind() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+include = ["workflow", "makefile"]
+"#,
+    );
+
+    context.command().assert().failure();
+}
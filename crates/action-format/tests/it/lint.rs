@@ -0,0 +1,1858 @@
+use crate::action_format_snapshot;
+use crate::common::TestContext;
+
+#[test]
+fn test_lint_clean_workflow() {
+    let context = TestContext::new();
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+permissions:
+  contents: read
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    timeout-minutes: 10
+    steps:
+      - uses: actions/checkout@b4ffde65f46336ab88eb53be808477a3936bae11 # v4.1.1
+      - uses: ./.github/actions/setup
+",
+    );
+    context.action(
+        "setup",
+        r"name: Setup
+runs:
+  using: composite
+  steps:
+    - run: echo setup
+      shell: bash
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_duplicate_key() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF001"]
+"#,
+    );
+    let workflow = r"name: CI
+on: push
+jobs:
+    build:
+        runs-on: ubuntu-latest
+        runs-on: windows-latest
+        steps:
+            - name: Build
+              run: cargo build
+              name: Test
+";
+    context.workflow("ci.yml", workflow);
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/workflows/ci.yml:6:9: error[AF001] Duplicate key `runs-on`
+    .github/workflows/ci.yml:10:15: error[AF001] Duplicate key `name`
+    Found 2 problems
+
+    ----- stderr -----
+    ");
+
+    // Linting never modifies files
+    assert_eq!(context.read_workflow("ci.yml"), workflow);
+}
+
+#[test]
+fn test_lint_actions_and_workflows() {
+    let context = TestContext::new();
+    context.workflow(
+        "ci.yml",
+        r"on: push
+on: pull_request
+jobs: {}
+",
+    );
+    context.action(
+        "setup",
+        r"name: Setup
+name: Setup again
+runs:
+  using: composite
+  steps: []
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/actions/setup/action.yml:2:1: error[AF001] Duplicate key `name`
+    .github/workflows/ci.yml:2:1: error[AF001] Duplicate key `on`
+    Found 2 problems
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_ignore_rule() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+ignore = ["AF001"]
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"on: push
+on: pull_request
+jobs: {}
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_select_rule() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF001"]
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"on: push
+on: pull_request
+jobs: {}
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/workflows/ci.yml:2:1: error[AF001] Duplicate key `on`
+    Found 1 problem
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_unknown_selector() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF999"]
+"#,
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r#"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: failed to parse config file '.github/action-format.toml'
+      Caused by: TOML parse error at line 2, column 10
+      |
+    2 | select = ["AF999"]
+      |          ^^^^^^^^^
+    unknown rule selector `AF999`
+    "#);
+}
+
+#[test]
+fn test_lint_invalid_yaml() {
+    let context = TestContext::new();
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build: [
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: .github/workflows/ci.yml: Invalid YAML at line 5, column 1: while parsing a node, did not find expected node content
+    ");
+}
+
+#[test]
+fn test_lint_unpinned_action() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF002"]
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: Swatinem/rust-cache@main
+      - uses: actions/cache/save@b4ffde6
+      - uses: docker/setup-buildx-action@b4ffde65f46336ab88eb53be808477a3936bae11
+      - uses: docker/login-action@b4ffde65f46336ab88eb53be808477a3936bae11 # v3.0.0
+      - uses: ./.github/actions/setup
+      - uses: docker://alpine:3.20
+  release:
+    uses: octo-org/workflows/.github/workflows/release.yml@v1
+  local:
+    uses: ./.github/workflows/reusable.yml
+",
+    );
+    context.action(
+        "setup",
+        r"name: Setup
+runs:
+  using: composite
+  steps:
+    - uses: dtolnay/rust-toolchain@stable
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/actions/setup/action.yml:5:13: error[AF002] `dtolnay/rust-toolchain@stable` is not pinned to a full commit SHA
+    .github/workflows/ci.yml:7:15: error[AF002] `actions/checkout@v4` is not pinned to a full commit SHA
+    .github/workflows/ci.yml:8:15: error[AF002] `Swatinem/rust-cache@main` is not pinned to a full commit SHA
+    .github/workflows/ci.yml:9:15: error[AF002] `actions/cache/save@b4ffde6` is not pinned to a full commit SHA
+    .github/workflows/ci.yml:10:15: error[AF002] `docker/setup-buildx-action` is pinned to a commit SHA without a version comment
+    .github/workflows/ci.yml:15:11: error[AF002] `octo-org/workflows/.github/workflows/release.yml@v1` is not pinned to a full commit SHA
+    Found 6 problems
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_unpinned_action_trusted() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF002"]
+
+[lint.unpinned_action]
+trusted = ["actions/*", "dtolnay/rust-toolchain"]
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/cache/save@v4
+      - uses: dtolnay/rust-toolchain@stable
+      - uses: dtolnay/install@master
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/workflows/ci.yml:10:15: error[AF002] `dtolnay/install@master` is not pinned to a full commit SHA
+    Found 1 problem
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_permissions() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF003"]
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"# CI
+name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+  release:
+    runs-on: ubuntu-latest
+    permissions: write-all
+",
+    );
+    context.workflow(
+        "jobs.yml",
+        r"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    permissions:
+      contents: read
+",
+    );
+    context.workflow(
+        "target.yml",
+        r"on:
+  pull_request_target:
+    types: [opened]
+permissions:
+  contents: read
+  pull-requests: write
+jobs:
+  label:
+    runs-on: ubuntu-latest
+    permissions:
+      issues: write
+      contents: read
+",
+    );
+    context.action(
+        "setup",
+        r"name: Setup
+runs:
+  using: composite
+  steps: []
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/workflows/ci.yml:2:1: error[AF003] Workflow has no top-level `permissions` block
+    .github/workflows/ci.yml:9:18: error[AF003] `write-all` grants every permission, list the scopes that are needed instead
+    .github/workflows/target.yml:6:18: error[AF003] `pull-requests: write` in a workflow triggered by `pull_request_target`
+    .github/workflows/target.yml:11:15: error[AF003] `issues: write` in a workflow triggered by `pull_request_target`
+    Found 4 problems
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_permissions_top_level_not_required() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF003"]
+
+[lint.permissions]
+require_top_level = false
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: [push, pull_request_target]
+jobs:
+  build:
+    runs-on: ubuntu-latest
+",
+    );
+    context.workflow(
+        "release.yml",
+        r"name: Release
+on: push
+permissions: write-all
+jobs: {}
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/workflows/release.yml:3:14: error[AF003] `write-all` grants every permission, list the scopes that are needed instead
+    Found 1 problem
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_script_injection() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF004"]
+"#,
+    );
+    let workflow = r#"name: Triage
+on: issues
+jobs:
+  triage:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "${{ github.event.issue.title }}"
+      - run: |
+          echo "${{ github.event.commits[0].message }}"
+          echo "${{ github.event['pull_request']['title'] }}"
+      - run: echo "${{ github.event.issue.number }} ${{ github.sha }}"
+      - uses: actions/github-script@v7
+        with:
+          script: console.log("${{ github.event.comment.body }}")
+      - uses: actions/other-script@v1
+        with:
+          script: console.log("${{ github.event.comment.body }}")
+      - run: print("${{ github.head_ref }}")
+        shell: python
+"#;
+    context.workflow("triage.yml", workflow);
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/workflows/triage.yml:7:20: error[AF004] `github.event.issue.title` is expanded into the `run` script, pass it in an environment variable instead
+    .github/workflows/triage.yml:9:17: error[AF004] `github.event.commits.*.message` is expanded into the `run` script, pass it in an environment variable instead
+    .github/workflows/triage.yml:10:17: error[AF004] `github.event.pull_request.title` is expanded into the `run` script, pass it in an environment variable instead
+    .github/workflows/triage.yml:14:32: error[AF004] `github.event.comment.body` is expanded into the `script` script, pass it in an environment variable instead
+    .github/workflows/triage.yml:18:21: error[AF004] `github.head_ref` is expanded into the `run` script, pass it in an environment variable instead
+    Found 5 problems (2 fixable with `--fix`)
+
+    ----- stderr -----
+    "#);
+
+    // Linting never modifies files
+    assert_eq!(context.read_workflow("triage.yml"), workflow);
+}
+
+#[test]
+fn test_lint_fix_script_injection() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF004"]
+"#,
+    );
+    context.workflow(
+        "triage.yml",
+        r#"name: Triage
+on: issues
+jobs:
+  triage:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "${{ github.event.issue.title }}"
+
+      - name: Comment
+        run: |
+          echo "${{ github.event.issue.title }}"
+          echo ${{ github.event.issue.body }} ${{ github.event.issue.title }}
+        env:
+          GREETING: hello
+
+      - uses: actions/github-script@v7
+        with:
+          script: |
+            console.log('${{ github.event.comment.body }}')
+
+      - run: echo ${{ github.event.pull_request.head.ref || 'main' }}
+  windows:
+    runs-on: windows-latest
+    steps:
+      - run: echo ${{ github.head_ref }}
+"#,
+    );
+
+    action_format_snapshot!(context.filters(), context.command().args(["lint", "--fix"]), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/workflows/triage.yml:27:19: error[AF004] `github.event.pull_request.head.ref` is expanded into the `run` script, pass it in an environment variable instead
+    Fixed 5 problems
+    Found 1 problem
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_workflow("triage.yml");
+    insta::assert_snapshot!(content, @r#"
+    name: Triage
+    on: issues
+    jobs:
+      triage:
+        runs-on: ubuntu-latest
+        steps:
+          - run: echo "$ISSUE_TITLE"
+            env:
+              ISSUE_TITLE: ${{ github.event.issue.title }}
+
+          - name: Comment
+            run: |
+              echo "$ISSUE_TITLE"
+              echo "$ISSUE_BODY" "$ISSUE_TITLE"
+            env:
+              GREETING: hello
+              ISSUE_TITLE: ${{ github.event.issue.title }}
+              ISSUE_BODY: ${{ github.event.issue.body }}
+
+          - uses: actions/github-script@v7
+            with:
+              script: |
+                console.log(process.env.COMMENT_BODY)
+            env:
+              COMMENT_BODY: ${{ github.event.comment.body }}
+
+          - run: echo ${{ github.event.pull_request.head.ref || 'main' }}
+      windows:
+        runs-on: windows-latest
+        steps:
+          - run: echo $env:HEAD_REF
+            env:
+              HEAD_REF: ${{ github.head_ref }}
+    "#);
+}
+
+#[test]
+fn test_lint_fix_script_injection_quoting() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF004"]
+"#,
+    );
+    context.workflow(
+        "triage.yml",
+        r#"name: Triage
+on: issues
+jobs:
+  triage:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo 'Title is ${{ github.event.issue.title }}'
+      - run: git checkout -b fix-${{ github.head_ref }}_1
+      - run: |
+          cat <<EOF
+          Body: ${{ github.event.issue.body }}
+          EOF
+      - run: |
+          cat <<'EOF'
+          Comment: ${{ github.event.comment.body }}
+          EOF
+      - uses: actions/github-script@v7
+        with:
+          script: |
+            core.info("Title: ${{ github.event.issue.title }}")
+            core.info(`Body: ${{ github.event.issue.body }}`)
+      - uses: actions/github-script@v7
+        with:
+          script: const ref = ${{ github.head_ref }}
+  windows:
+    runs-on: windows-latest
+    steps:
+      - run: echo 'Title is ${{ github.event.issue.title }}'
+"#,
+    );
+
+    // Expressions in quoted here documents, JavaScript code outside string literals and
+    // PowerShell single-quoted strings can't read a variable without changing the script
+    action_format_snapshot!(context.filters(), context.command().args(["lint", "--fix"]), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/workflows/triage.yml:21:20: error[AF004] `github.event.comment.body` is expanded into the `run` script, pass it in an environment variable instead
+    .github/workflows/triage.yml:33:31: error[AF004] `github.head_ref` is expanded into the `script` script, pass it in an environment variable instead
+    .github/workflows/triage.yml:37:29: error[AF004] `github.event.issue.title` is expanded into the `run` script, pass it in an environment variable instead
+    Fixed 5 problems
+    Found 3 problems
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_workflow("triage.yml");
+    insta::assert_snapshot!(content, @r#"
+    name: Triage
+    on: issues
+    jobs:
+      triage:
+        runs-on: ubuntu-latest
+        steps:
+          - run: echo 'Title is '"$ISSUE_TITLE"''
+            env:
+              ISSUE_TITLE: ${{ github.event.issue.title }}
+          - run: git checkout -b fix-"$HEAD_REF"_1
+            env:
+              HEAD_REF: ${{ github.head_ref }}
+          - run: |
+              cat <<EOF
+              Body: $ISSUE_BODY
+              EOF
+            env:
+              ISSUE_BODY: ${{ github.event.issue.body }}
+          - run: |
+              cat <<'EOF'
+              Comment: ${{ github.event.comment.body }}
+              EOF
+          - uses: actions/github-script@v7
+            with:
+              script: |
+                core.info("Title: " + process.env.ISSUE_TITLE + "")
+                core.info(`Body: ${process.env.ISSUE_BODY}`)
+            env:
+              ISSUE_TITLE: ${{ github.event.issue.title }}
+              ISSUE_BODY: ${{ github.event.issue.body }}
+          - uses: actions/github-script@v7
+            with:
+              script: const ref = ${{ github.head_ref }}
+      windows:
+        runs-on: windows-latest
+        steps:
+          - run: echo 'Title is ${{ github.event.issue.title }}'
+    "#);
+}
+
+#[test]
+fn test_lint_fix_script_injection_crlf() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF004"]
+"#,
+    );
+    context.workflow(
+        "triage.yml",
+        &"name: Triage
+on: issues
+jobs:
+  triage:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo \"${{ github.event.issue.title }}\"
+      - run: echo \"${{ github.event.issue.body }}\"
+        env:
+          GREETING: hello
+"
+        .replace('\n', "\r\n"),
+    );
+
+    context.command().args(["lint", "--fix"]).assert().success();
+
+    let content = context.read_workflow("triage.yml");
+    assert_eq!(
+        content.matches('\n').count(),
+        content.matches("\r\n").count()
+    );
+    insta::assert_snapshot!(content.replace("\r\n", "␍\n"), @r#"
+    name: Triage␍
+    on: issues␍
+    jobs:␍
+      triage:␍
+        runs-on: ubuntu-latest␍
+        steps:␍
+          - run: echo "$ISSUE_TITLE"␍
+            env:␍
+              ISSUE_TITLE: ${{ github.event.issue.title }}␍
+          - run: echo "$ISSUE_BODY"␍
+            env:␍
+              GREETING: hello␍
+              ISSUE_BODY: ${{ github.event.issue.body }}␍
+    "#);
+}
+
+#[test]
+fn test_lint_timeout_minutes() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF005"]
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps: []
+  test:
+    runs-on: ubuntu-latest
+    timeout-minutes: 20
+    steps: []
+  release:
+    uses: ./.github/workflows/release.yml
+",
+    );
+
+    // Warnings don't fail the run, and there is no fix without a configured default
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .github/workflows/ci.yml:4:3: warning[AF005] Job `build` has no `timeout-minutes`
+    Found 1 problem
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_fix_timeout_minutes() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF005"]
+
+[lint.timeout_minutes]
+default = 30
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build:
+    name: Build
+    runs-on:
+      - self-hosted
+      - linux
+    env:
+      CARGO_TERM_COLOR: always
+
+    strategy:
+      matrix:
+        os: [ubuntu-latest]
+    steps: []
+  lint:
+    steps: []
+  flow: {runs-on: ubuntu-latest, steps: []}
+  empty: {}
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().args(["lint", "--fix"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .github/workflows/ci.yml:20:3: warning[AF005] Job `flow` has no `timeout-minutes`
+    .github/workflows/ci.yml:21:3: warning[AF005] Job `empty` has no `timeout-minutes`
+    Fixed 2 problems
+    Found 2 problems
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    name: CI
+    on: push
+    jobs:
+      build:
+        name: Build
+        runs-on:
+          - self-hosted
+          - linux
+        env:
+          CARGO_TERM_COLOR: always
+        timeout-minutes: 30
+
+        strategy:
+          matrix:
+            os: [ubuntu-latest]
+        steps: []
+      lint:
+        timeout-minutes: 30
+        steps: []
+      flow: {runs-on: ubuntu-latest, steps: []}
+      empty: {}
+    ");
+}
+
+#[test]
+fn test_lint_fix_timeout_minutes_crlf() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF005"]
+
+[lint.timeout_minutes]
+default = 30
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        &"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps: []
+  lint:
+    steps: []"
+            .replace('\n', "\r\n"),
+    );
+
+    context.command().args(["lint", "--fix"]).assert().success();
+
+    let content = context.read_workflow("ci.yml");
+    assert_eq!(
+        content.matches('\n').count(),
+        content.matches("\r\n").count()
+    );
+    insta::assert_snapshot!(content.replace("\r\n", "␍\n"), @"
+    name: CI␍
+    on: push␍
+    jobs:␍
+      build:␍
+        runs-on: ubuntu-latest␍
+        timeout-minutes: 30␍
+        steps: []␍
+      lint:␍
+        timeout-minutes: 30␍
+        steps: []
+    ");
+}
+
+#[test]
+fn test_lint_needs() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF006"]
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    outputs:
+      version: ${{ steps.version.outputs.version }}
+    steps: []
+  test:
+    needs: biuld
+    runs-on: ubuntu-latest
+    steps: []
+  release:
+    needs: [build, publish]
+    if: needs.build.outputs.version != ''
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo ${{ needs.build.outputs.verison }}
+      - run: |
+          echo ${{ needs.test.result }}
+          echo ${{ needs.build.outputs.version }}
+  publish:
+    needs: release
+    uses: ./.github/workflows/publish.yml
+  loop:
+    needs: loop
+    runs-on: ubuntu-latest
+    steps: []
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/workflows/ci.yml:10:12: error[AF006] Job `test` needs unknown job `biuld`, did you mean `build`?
+    .github/workflows/ci.yml:18:23: error[AF006] Job `build` has no output `verison`
+    .github/workflows/ci.yml:20:20: error[AF006] Job `release` uses `needs.test` but doesn't list `test` in `needs`
+    .github/workflows/ci.yml:23:12: error[AF006] Jobs form a dependency cycle: `release` -> `publish` -> `release`
+    .github/workflows/ci.yml:26:12: error[AF006] Jobs form a dependency cycle: `loop` -> `loop`
+    Found 5 problems
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_needs_wildcard() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF006"]
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps: []
+  notify:
+    needs: build
+    if: ${{ always() && contains(needs.*.result, 'failure') }}
+    runs-on: ubuntu-latest
+    steps: []
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_step_id() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF007"]
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    outputs:
+      version: ${{ steps.version.outputs.version }}
+      digest: ${{ steps.digest.outputs.digest }}
+    steps:
+      - run: echo ${{ steps.build.outputs.path }}
+      - id: version
+        run: echo version=1.0 >> $GITHUB_OUTPUT
+      - id: build
+        if: steps.version.outputs.version != ''
+        run: echo ${{ steps.version.outputs.version }}
+      - id: build
+        run: echo ${{ steps.biuld.outcome }}
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo ${{ steps.version.outputs.version }}
+",
+    );
+    context.action(
+        "setup",
+        r"name: Setup
+outputs:
+  path:
+    value: ${{ steps.install.outputs.path }}
+runs:
+  using: composite
+  steps:
+    - id: setup
+      shell: bash
+      run: echo ${{ steps.setup.outcome }}
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/actions/setup/action.yml:4:16: error[AF007] No step in the action has id `install`
+    .github/actions/setup/action.yml:10:21: error[AF007] Step `setup` hasn't run yet when this step runs
+    .github/workflows/ci.yml:8:19: error[AF007] No step in job `build` has id `digest`
+    .github/workflows/ci.yml:10:23: error[AF007] Step `build` hasn't run yet when this step runs
+    .github/workflows/ci.yml:16:13: error[AF007] Step id `build` is used more than once in job `build`
+    .github/workflows/ci.yml:17:23: error[AF007] No step in job `build` has id `biuld`
+    .github/workflows/ci.yml:21:23: error[AF007] No step in job `test` has id `version`
+    Found 7 problems
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_schema() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF008"]
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r#"name: CI
+on:
+  pushh:
+    branches: [main]
+  pull_request:
+    typs: [opened]
+permissions:
+  contents: admin
+jobs:
+  build:
+    runs_on: ubuntu-latest
+    timeout-minutes: "ten"
+    strategy:
+      fail-fast: ${{ github.event_name == 'push' }}
+      max-parallel: two
+    steps:
+      - run: echo hello
+        env:
+          NAMES: [a, b]
+"#,
+    );
+    context.workflow(
+        "release.yml",
+        r"on: [push, releases]
+",
+    );
+    context.action(
+        "setup",
+        r"name: Setup
+runs_on: ubuntu-latest
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/workflows/ci.yml:3:3: error[AF008] Unknown event `pushh` in `on`, did you mean `push`?
+    .github/workflows/ci.yml:6:5: error[AF008] Unknown key `typs` in `on.pull_request`, did you mean `types`?
+    .github/workflows/ci.yml:8:13: error[AF008] `admin` is not a valid value for `permissions.contents`, expected `read`, `write` or `none`
+    .github/workflows/ci.yml:11:5: error[AF008] Unknown key `runs_on` in `jobs.build`, did you mean `runs-on`?
+    .github/workflows/ci.yml:12:22: error[AF008] `jobs.build.timeout-minutes` must be a number, not a string
+    .github/workflows/ci.yml:15:21: error[AF008] `jobs.build.strategy.max-parallel` must be a number, not a string
+    .github/workflows/ci.yml:19:18: error[AF008] `jobs.build.steps[0].env.NAMES` must be a string, a number or a boolean, not a sequence
+    .github/workflows/release.yml:1:1: error[AF008] Missing required key `jobs`
+    .github/workflows/release.yml:1:12: error[AF008] Unknown event `releases` in `on[1]`, did you mean `release`?
+    Found 9 problems
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_fix_deprecated_command() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF009"]
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r#"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - id: version
+        run: echo "::set-output name=version::1.0"
+      - run: |
+          echo "::save-state name=pid::$PID"
+          echo ::add-path::/opt/bin
+          echo "::set-env name=TARGET::release" && make
+  windows:
+    runs-on: windows-latest
+    steps:
+      - run: echo '::set-output name=version::1.0'
+"#,
+    );
+
+    action_format_snapshot!(context.filters(), context.command().args(["lint", "--fix"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .github/workflows/ci.yml:12:17: warning[AF009] `::set-env` is disabled, write to `$GITHUB_ENV` instead
+    Fixed 4 problems
+    Found 1 problem
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r#"
+    name: CI
+    on: push
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        steps:
+          - id: version
+            run: echo "version=1.0" >> "$GITHUB_OUTPUT"
+          - run: |
+              echo "pid=$PID" >> "$GITHUB_STATE"
+              echo /opt/bin >> "$GITHUB_PATH"
+              echo "::set-env name=TARGET::release" && make
+      windows:
+        runs-on: windows-latest
+        steps:
+          - run: echo 'version=1.0' >> $env:GITHUB_OUTPUT
+    "#);
+}
+
+#[test]
+fn test_lint_deprecated_action() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF010"]
+
+[lint.deprecated_action.versions]
+"octo/legacy@v1" = "octo/modern@v2"
+"octo/archived" = ""
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v2
+      - uses: actions/upload-artifact@a8a3f3ad30e3422c9c7b888a15615d19a852ae32 # v3.1.3
+      - uses: actions/cache@v4
+      - uses: actions/setup-node@v30
+      - uses: octo/legacy@v1.2
+      - uses: octo/archived@main
+",
+    );
+    context.action(
+        "old",
+        r"name: Old
+runs:
+  using: node16
+  main: index.js
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .github/actions/old/action.yml:3:10: warning[AF010] `node16` is no longer supported, use `node20` instead
+    .github/workflows/ci.yml:7:15: warning[AF010] `actions/checkout@v2` is deprecated, use `actions/checkout@v4` instead
+    .github/workflows/ci.yml:8:15: warning[AF010] `actions/upload-artifact@v3` is deprecated, use `actions/upload-artifact@v4` instead
+    .github/workflows/ci.yml:11:15: warning[AF010] `octo/legacy@v1` is deprecated, use `octo/modern@v2` instead
+    .github/workflows/ci.yml:12:15: warning[AF010] `octo/archived` is deprecated
+    Found 5 problems
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_inputs() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF011"]
+"#,
+    );
+    context.workflow(
+        "deploy.yml",
+        r"on:
+  workflow_call:
+    inputs:
+      environment:
+        type: string
+        required: true
+      dry-run:
+        type: boolean
+        default: yes
+      retries:
+        type: number
+        default: three
+      region:
+        type: string
+    secrets:
+      token:
+        required: true
+      extra:
+    outputs:
+      url:
+        value: ${{ jobs.deploy.outputs.url }}
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    if: ${{ !inputs.dry-run }}
+    steps:
+      - run: ./deploy ${{ inputs.environment }} ${{ inputs.retries }} ${{ inputs.zone }}
+        env:
+          TOKEN: ${{ secrets.token }}
+          NPM_TOKEN: ${{ secrets.NPM_TOKEN }}
+          GITHUB_TOKEN: ${{ secrets.GITHUB_TOKEN }}
+",
+    );
+    context.workflow(
+        "manual.yml",
+        r"on:
+  push:
+  workflow_dispatch:
+    inputs:
+      level:
+        type: choice
+        options: [debug, info]
+        default: trace
+      name:
+        default: world
+jobs:
+  run:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo ${{ github.event.inputs.level }} ${{ secrets.NPM_TOKEN }}
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/workflows/deploy.yml:9:18: error[AF011] Default `yes` of boolean input `dry-run` is not `true` or `false`
+    .github/workflows/deploy.yml:12:18: error[AF011] Default `three` of number input `retries` is not a number
+    .github/workflows/deploy.yml:13:7: error[AF011] Input `region` is declared but never used
+    .github/workflows/deploy.yml:18:7: error[AF011] Secret `extra` is declared but never used
+    .github/workflows/deploy.yml:27:75: error[AF011] Input `zone` is used but not declared
+    .github/workflows/deploy.yml:30:26: error[AF011] Secret `NPM_TOKEN` is used but not declared
+    .github/workflows/manual.yml:8:18: error[AF011] Default `trace` of choice input `level` is not one of its options
+    .github/workflows/manual.yml:9:7: error[AF011] Input `name` is declared but never used
+    Found 8 problems
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_workflow_call() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF012"]
+"#,
+    );
+    context.workflow(
+        "deploy.yml",
+        r"on:
+  workflow_call:
+    inputs:
+      environment:
+        type: string
+        required: true
+      dry-run:
+        type: boolean
+      retries:
+        type: number
+    secrets:
+      token:
+        required: true
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy
+",
+    );
+    context.workflow(
+        "build.yml",
+        r"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+",
+    );
+    context.workflow(
+        "ci.yml",
+        r"on: push
+jobs:
+  staging:
+    uses: ./.github/workflows/deploy.yml
+    with:
+      enviroment: staging
+      dry-run: yes
+      retries: ${{ github.run_attempt }}
+    secrets:
+      tokn: ${{ secrets.TOKEN }}
+  production:
+    uses: ./.github/workflows/deploy.yml
+    with:
+      environment: production
+      retries: three
+    secrets: inherit
+  build:
+    uses: ./.github/workflows/build.yml
+  release:
+    uses: ./.github/workflows/release.yml
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/workflows/ci.yml:4:11: error[AF012] Job `staging` doesn't pass required input `environment` of `./.github/workflows/deploy.yml`
+    .github/workflows/ci.yml:4:11: error[AF012] Job `staging` doesn't pass required secret `token` of `./.github/workflows/deploy.yml`
+    .github/workflows/ci.yml:6:7: error[AF012] `./.github/workflows/deploy.yml` has no input `enviroment`, did you mean `environment`?
+    .github/workflows/ci.yml:7:16: error[AF012] Input `dry-run` of `./.github/workflows/deploy.yml` must be `true` or `false`
+    .github/workflows/ci.yml:10:7: error[AF012] `./.github/workflows/deploy.yml` has no secret `tokn`, did you mean `token`?
+    .github/workflows/ci.yml:15:16: error[AF012] Input `retries` of `./.github/workflows/deploy.yml` must be a number
+    .github/workflows/ci.yml:18:11: error[AF012] `./.github/workflows/build.yml` isn't triggered by `workflow_call`
+    .github/workflows/ci.yml:20:11: error[AF012] Reusable workflow `./.github/workflows/release.yml` doesn't exist
+    Found 8 problems
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_action_inputs() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF013"]
+"#,
+    );
+    context.action(
+        "setup",
+        r"name: Setup
+inputs:
+  toolchain:
+    required: true
+  components:
+    required: true
+    default: rustfmt
+  cache:
+    required: false
+runs:
+  using: composite
+  steps:
+    - uses: ./.github/actions/build
+      with:
+        target: release
+",
+    );
+    context.action(
+        "build",
+        r"name: Build
+runs:
+  using: composite
+  steps:
+    - run: make
+      shell: bash
+",
+    );
+    context.workflow(
+        "ci.yml",
+        r"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: ./.github/actions/setup
+        with:
+          toolchin: stable
+          Cache: true
+      - uses: ./.github/actions/setup
+        with:
+          toolchain: stable
+      - uses: ./.github/actions/release
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/actions/setup/action.yml:15:9: error[AF013] `./.github/actions/build` has no input `target`
+    .github/workflows/ci.yml:6:15: error[AF013] Required input `toolchain` of `./.github/actions/setup` isn't passed
+    .github/workflows/ci.yml:8:11: error[AF013] `./.github/actions/setup` has no input `toolchin`, did you mean `toolchain`?
+    .github/workflows/ci.yml:13:15: error[AF013] Local action `./.github/actions/release` doesn't exist
+    Found 4 problems
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_cron() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF014", "AF015"]
+"#,
+    );
+    context.workflow(
+        "nightly.yml",
+        r#"on:
+  schedule:
+    - cron: "0 2 * * *"
+    - cron: "0 25 * * *"
+    - cron: "0 2 * *"
+    - cron: "@daily"
+    - cron: "CRON_TZ=Europe/Paris 0 2 * * *"
+    - cron: "*/2 * * * *"
+    - cron: "30 9 * * MON-FRI"
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#,
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/workflows/nightly.yml:4:13: error[AF014] Invalid hour `25` in cron expression `0 25 * * *`, expected 0-23
+    .github/workflows/nightly.yml:5:13: error[AF014] Cron expression `0 2 * *` has 4 fields, expected 5: minute, hour, day of month, month and day of week
+    .github/workflows/nightly.yml:6:13: error[AF014] `@daily` isn't supported, use a cron expression with 5 fields
+    .github/workflows/nightly.yml:7:13: error[AF014] Schedules always run in UTC, `CRON_TZ` isn't supported
+    .github/workflows/nightly.yml:8:13: warning[AF015] `*/2 * * * *` runs every 2 minutes, but GitHub runs schedules at most every 5 minutes
+    Found 5 problems
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_explain() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF014"]
+"#,
+    );
+    context.workflow(
+        "nightly.yml",
+        r#"on:
+  schedule:
+    - cron: "0 2 * * *"
+    - cron: "*/15 * * * *"
+    - cron: "30 9 * * MON-FRI"
+    - cron: "0 0 1,15 JAN,JUL *"
+    - cron: "0 0 */10 * *"
+    - cron: "0 61 * * *"
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#,
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint").arg("--explain"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/workflows/nightly.yml:3:13: note: `0 2 * * *` runs at 02:00 UTC every day
+    .github/workflows/nightly.yml:4:13: note: `*/15 * * * *` runs every 15 minutes
+    .github/workflows/nightly.yml:5:13: note: `30 9 * * MON-FRI` runs at 09:30 UTC on Monday through Friday
+    .github/workflows/nightly.yml:6:13: note: `0 0 1,15 JAN,JUL *` runs at 00:00 UTC on days 1 and 15 of the month in January and July
+    .github/workflows/nightly.yml:7:13: note: `0 0 */10 * *` runs at 00:00 UTC on days 1, 11, 21 and 31 of the month
+    .github/workflows/nightly.yml:8:13: error[AF014] Invalid hour `61` in cron expression `0 61 * * *`, expected 0-23
+    Found 1 problem
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_fix_shell_script() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF016"]
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r#"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "version=${{ github.ref_name }}" >> $GITHUB_OUTPUT
+      - run: |
+          # List the packages, $GITHUB_ENV isn't used
+          cargo metadata | jq '.packages' > packages.json
+          echo "path=$GITHUB_PATH" >> ${GITHUB_STEP_SUMMARY}
+      - run: |
+          set +e
+          cd ${{ inputs.directory }}
+          cd docs || exit
+          if cd site; then make; fi
+      - run: |
+          cat <<EOF
+          path is $GITHUB_OUTPUT
+          EOF
+          cat <<-'END' >> $GITHUB_STEP_SUMMARY
+          $GITHUB_ENV
+          END
+      - shell: bash
+        run: cargo metadata | jq '.packages'
+      - shell: bash -e {0}
+        run: |
+          set -euo pipefail
+          cargo metadata | jq '.packages'
+"#,
+    );
+
+    action_format_snapshot!(context.filters(), context.command().args(["lint", "--fix"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .github/workflows/ci.yml:10:26: warning[AF016] Failures in this pipeline are ignored, add `set -euo pipefail` or use `shell: bash`
+    .github/workflows/ci.yml:14:11: warning[AF016] `cd` can fail without stopping the script, use `cd … || exit`
+    Fixed 3 problems
+    Found 2 problems
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r#"
+    name: CI
+    on: push
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        steps:
+          - run: echo "version=${{ github.ref_name }}" >> "$GITHUB_OUTPUT"
+          - run: |
+              # List the packages, $GITHUB_ENV isn't used
+              cargo metadata | jq '.packages' > packages.json
+              echo "path=$GITHUB_PATH" >> "${GITHUB_STEP_SUMMARY}"
+          - run: |
+              set +e
+              cd ${{ inputs.directory }}
+              cd docs || exit
+              if cd site; then make; fi
+          - run: |
+              cat <<EOF
+              path is $GITHUB_OUTPUT
+              EOF
+              cat <<-'END' >> "$GITHUB_STEP_SUMMARY"
+              $GITHUB_ENV
+              END
+          - shell: bash
+            run: cargo metadata | jq '.packages'
+          - shell: bash -e {0}
+            run: |
+              set -euo pipefail
+              cargo metadata | jq '.packages'
+    "#);
+}
+
+#[test]
+#[cfg(unix)]
+fn test_lint_external_shellcheck() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF016"]
+"#,
+    );
+    // Report the first `$` of each script, with the shell it was checked as.
+    context.bin(
+        "shellcheck",
+        r#"#!/bin/sh
+shell="${2#--shell=}"
+awk -v shell="$shell" '
+  index($0, "$") && !found { found = 1; line = NR; column = index($0, "$") }
+  END {
+    printf "{\"comments\":["
+    if (found) printf "{\"line\":%d,\"column\":%d,\"level\":\"warning\",\"code\":2086,\"message\":\"Double quote to prevent globbing and word splitting (%s).\"}", line, column, shell
+    printf "]}"
+  }
+' "$3"
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r#"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo ${{ github.ref_name }} $VERSION
+      - shell: sh
+        run: |
+          make
+          cp target/$TARGET dist
+      - shell: bash
+        run: |
+          echo "version=$VERSION" >> $GITHUB_OUTPUT
+"#,
+    );
+
+    action_format_snapshot!(context.filters(), context.command().args(["lint", "--external", "shellcheck"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .github/workflows/ci.yml:7:42: warning[SC2086] Double quote to prevent globbing and word splitting (bash).
+    .github/workflows/ci.yml:11:21: warning[SC2086] Double quote to prevent globbing and word splitting (sh).
+    .github/workflows/ci.yml:14:25: warning[SC2086] Double quote to prevent globbing and word splitting (bash).
+    .github/workflows/ci.yml:14:38: warning[AF016] Quote `$GITHUB_OUTPUT` to prevent word splitting
+    Found 4 problems (1 fixable with `--fix`)
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_lint_external_shellcheck_flow_scalars() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF016"]
+"#,
+    );
+    // Report the first `$` of each script, with the number of lines shellcheck was given.
+    context.bin(
+        "shellcheck",
+        r#"#!/bin/sh
+awk '
+  index($0, "$") && !found { found = 1; line = NR; column = index($0, "$") }
+  END {
+    printf "{\"comments\":["
+    if (found) printf "{\"line\":%d,\"column\":%d,\"level\":\"warning\",\"code\":2086,\"message\":\"Double quote to prevent globbing and word splitting (lines: %d).\"}", line, column, NR
+    printf "]}"
+  }
+' "$3"
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r#"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+          && cp target/$TARGET dist
+
+          && ls $DIST
+      - run: "echo \"a\" \
+          $VERSION"
+"#,
+    );
+
+    action_format_snapshot!(context.filters(), context.command().args(["lint", "--external", "shellcheck"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .github/workflows/ci.yml:8:24: warning[SC2086] Double quote to prevent globbing and word splitting (lines: 2).
+    .github/workflows/ci.yml:12:11: warning[SC2086] Double quote to prevent globbing and word splitting (lines: 1).
+    Found 2 problems
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_lint_external_actionlint() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF005"]
+"#,
+    );
+    context.bin(
+        "actionlint",
+        r#"#!/bin/sh
+echo '[{"message":"property \"ref_nam\" is not defined in object type","filepath":"'"$3"'","line":7,"column":24,"kind":"expression"}]'
+exit 1
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo ${{ github.ref_nam }}
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().args(["lint", "--external", "actionlint"]), @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/workflows/ci.yml:4:3: warning[AF005] Job `build` has no `timeout-minutes`
+    .github/workflows/ci.yml:7:24: error[actionlint/expression] property "ref_nam" is not defined in object type
+    Found 2 problems
+
+    ----- stderr -----
+    "#);
+}
+
+#[test]
+fn test_lint_external_missing() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF005", "AF016"]
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo version=1.0 >> $GITHUB_OUTPUT
+",
+    );
+    context.workflow(
+        "release.yml",
+        r"name: Release
+on: push
+jobs:
+  release:
+    runs-on: ubuntu-latest
+    timeout-minutes: 10
+    steps:
+      - run: make release
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().args(["lint", "--fix", "--external", "shellcheck"]).env("PATH", ""), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+    .github/workflows/ci.yml:4:3: warning[AF005] Job `build` has no `timeout-minutes`
+    Fixed 1 problem
+    Found 1 problem
+
+    ----- stderr -----
+    error: Failed to run `shellcheck`, is it installed?
+    ");
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r#"
+    name: CI
+    on: push
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        steps:
+          - run: echo version=1.0 >> "$GITHUB_OUTPUT"
+    "#);
+}
+
+#[test]
+fn test_lint_references_in_string_literals() {
+    let context = TestContext::new();
+    context.config(
+        r#"
+[lint]
+select = ["AF006", "AF007"]
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - id: version
+        run: echo ${{ format('steps.{0}', 'x') }}
+      - if: contains(steps.version.outcome, 'needs.deploy')
+        run: echo ${{ format('needs.{0}.result', 'deploy') }}
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lint_format_flags_rejected() {
+    let context = TestContext::new();
+
+    context
+        .command()
+        .args(["lint", "--check"])
+        .assert()
+        .failure();
+}
@@ -1,3 +1,6 @@
 pub(crate) mod common;
 
 mod format;
+mod lint;
+mod model;
+mod pin;
@@ -0,0 +1,219 @@
+use action_format_core::{Action, Span, Workflow};
+
+#[test]
+fn test_model_steps_using_action() {
+    let workflow = Workflow::parse(
+        r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - id: cache
+        uses: actions/cache@v4
+        with:
+          path: target
+          key: ${{ runner.os }}
+  test:
+    needs: build
+    runs-on: [self-hosted, linux]
+    steps:
+      - uses: actions/cache/restore@v4
+      - run: cargo test
+        env:
+          RUST_BACKTRACE: 1
+",
+    )
+    .unwrap();
+
+    let cache: Vec<(&str, Span)> = workflow
+        .steps()
+        .filter(|(_, step)| step.action() == Some("actions/cache"))
+        .map(|(job, step)| (job.id.as_str(), step.span))
+        .collect();
+    assert_eq!(cache, [("build", Span { line: 8, column: 9 })]);
+
+    let build = workflow.job("build").unwrap();
+    assert_eq!(build.span, Span { line: 4, column: 3 });
+    let id = build.steps[1].id.as_ref().unwrap();
+    assert_eq!(id, "cache");
+    assert_eq!(
+        id.span,
+        Span {
+            line: 8,
+            column: 13
+        }
+    );
+    assert_eq!(build.steps[1].input("path"), Some("target"));
+
+    let test = workflow.job("test").unwrap();
+    assert_eq!(test.needs, ["build"]);
+    assert_eq!(
+        test.needs[0].span,
+        Span {
+            line: 14,
+            column: 12
+        }
+    );
+    assert_eq!(test.runs_on, ["self-hosted", "linux"]);
+    assert_eq!(test.steps[1].run.as_ref().unwrap(), "cargo test");
+    let (name, value) = &test.steps[1].env[0];
+    assert_eq!((name.as_str(), value.as_str()), ("RUST_BACKTRACE", "1"));
+    assert_eq!(
+        value.span,
+        Span {
+            line: 20,
+            column: 27
+        }
+    );
+    assert!(test.node.get("runs-on").is_some());
+}
+
+#[test]
+fn test_model_triggers() {
+    let workflow = Workflow::parse(
+        r"on:
+  push:
+    branches: [main]
+    tags: v*
+  pull_request:
+    types: [opened, synchronize]
+    paths-ignore:
+      - docs/**
+  schedule:
+    - cron: 0 0 * * 1
+  workflow_dispatch:
+jobs: {}
+",
+    )
+    .unwrap();
+
+    let events: Vec<&str> = workflow
+        .triggers
+        .iter()
+        .map(|trigger| trigger.event.as_str())
+        .collect();
+    assert_eq!(
+        events,
+        ["push", "pull_request", "schedule", "workflow_dispatch"]
+    );
+
+    let push = workflow.trigger("push").unwrap();
+    assert_eq!(push.branches, ["main"]);
+    assert_eq!(push.tags, ["v*"]);
+    assert_eq!(push.span, Span { line: 2, column: 3 });
+
+    let pull_request = workflow.trigger("pull_request").unwrap();
+    assert_eq!(pull_request.types, ["opened", "synchronize"]);
+    assert_eq!(pull_request.paths_ignore, ["docs/**"]);
+
+    let cron = &workflow.trigger("schedule").unwrap().cron;
+    assert_eq!(cron, &["0 0 * * 1"]);
+    assert_eq!(
+        cron[0].span,
+        Span {
+            line: 10,
+            column: 13
+        }
+    );
+
+    let workflow = Workflow::parse("on: [push, pull_request]\n").unwrap();
+    let events: Vec<&str> = workflow
+        .triggers
+        .iter()
+        .map(|trigger| trigger.event.as_str())
+        .collect();
+    assert_eq!(events, ["push", "pull_request"]);
+}
+
+#[test]
+fn test_model_matrix() {
+    let workflow = Workflow::parse(
+        r"on: push
+jobs:
+  test:
+    strategy:
+      matrix:
+        os: [ubuntu-latest, windows-latest]
+        rust: [stable, beta]
+        include:
+          - os: macos-latest
+            rust: stable
+        exclude:
+          - os: windows-latest
+            rust: beta
+    runs-on: ${{ matrix.os }}
+  dynamic:
+    strategy:
+      matrix: ${{ fromJSON(needs.setup.outputs.matrix) }}
+    runs-on: ubuntu-latest
+",
+    )
+    .unwrap();
+
+    let matrix = workflow.job("test").unwrap().matrix.as_ref().unwrap();
+    let dimensions: Vec<(&str, Vec<&str>)> = matrix
+        .dimensions
+        .iter()
+        .map(|(name, values)| {
+            (
+                name.as_str(),
+                values.iter().filter_map(|value| value.as_str()).collect(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        dimensions,
+        [
+            ("os", vec!["ubuntu-latest", "windows-latest"]),
+            ("rust", vec!["stable", "beta"]),
+        ]
+    );
+    assert_eq!(matrix.include.len(), 1);
+    assert_eq!(
+        matrix.exclude[0].get("os").and_then(|os| os.as_str()),
+        Some("windows-latest")
+    );
+    assert_eq!(matrix.expression, None);
+
+    let matrix = workflow.job("dynamic").unwrap().matrix.as_ref().unwrap();
+    assert!(matrix.dimensions.is_empty());
+    assert_eq!(
+        matrix.expression.as_ref().unwrap(),
+        "${{ fromJSON(needs.setup.outputs.matrix) }}"
+    );
+}
+
+#[test]
+fn test_model_composite_action() {
+    let action = Action::parse(
+        r"name: Setup
+runs:
+  using: composite
+  steps:
+    - uses: actions/setup-node@v4
+      with:
+        node-version: 20
+    - run: npm ci
+      shell: bash
+",
+    )
+    .unwrap();
+
+    assert_eq!(action.name.as_ref().unwrap(), "Setup");
+    let using = action.using.as_ref().unwrap();
+    assert_eq!(using, "composite");
+    assert_eq!(
+        using.span,
+        Span {
+            line: 3,
+            column: 10
+        }
+    );
+    assert_eq!(action.steps.len(), 2);
+    assert_eq!(action.steps[0].action(), Some("actions/setup-node"));
+    assert_eq!(action.steps[0].input("node-version"), Some("20"));
+    assert_eq!(action.steps[1].span, Span { line: 8, column: 7 });
+    assert_eq!(action.steps[1].shell.as_ref().unwrap(), "bash");
+}
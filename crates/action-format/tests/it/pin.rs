@@ -0,0 +1,274 @@
+use crate::action_format_snapshot;
+use crate::common::TestContext;
+
+const LOCK: &str = r"# owner/repo@ref sha
+actions/checkout@v4 b4ffde65f46336ab88eb53be808477a3936bae11
+actions/cache@v4 0c45773b623bea8c8e75f6c82b208c3cf94ea4f9
+Swatinem/rust-cache@v2 23bce251a8cd2ffc3c1075eaa2367cf899916d84
+octo-org/workflows@v1 8e5e7e5ab8b370d6c329ec480221332ada57f0ab
+";
+
+#[test]
+fn test_pin() {
+    let context = TestContext::new();
+    context.file(".github/actions.lock", LOCK);
+    context.workflow(
+        "ci.yml",
+        r#"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: "actions/cache/save@v4"
+      - uses: Swatinem/rust-cache@v2 # speeds up builds
+      - uses: actions/setup-node@1d0ff469b7ec7b3cb9d8673fde0c81c44821de2a # v4.2.0
+      - uses: ./.github/actions/setup
+      - uses: docker://alpine:3.20
+  release:
+    uses: octo-org/workflows/.github/workflows/release.yml@v1
+"#,
+    );
+    context.action(
+        "setup",
+        r"name: Setup
+runs:
+  using: composite
+  steps:
+    - uses: actions/checkout@v4
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("pin"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Pinned: .github/actions/setup/action.yml
+    Pinned: .github/workflows/ci.yml
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r#"
+    name: CI
+    on: push
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        steps:
+          - uses: actions/checkout@b4ffde65f46336ab88eb53be808477a3936bae11 # v4
+          - uses: "actions/cache/save@0c45773b623bea8c8e75f6c82b208c3cf94ea4f9" # v4
+          - uses: Swatinem/rust-cache@23bce251a8cd2ffc3c1075eaa2367cf899916d84 # v2 speeds up builds
+          - uses: actions/setup-node@1d0ff469b7ec7b3cb9d8673fde0c81c44821de2a # v4.2.0
+          - uses: ./.github/actions/setup
+          - uses: docker://alpine:3.20
+      release:
+        uses: octo-org/workflows/.github/workflows/release.yml@8e5e7e5ab8b370d6c329ec480221332ada57f0ab # v1
+    "#);
+
+    let content = context.read_action("setup");
+    insta::assert_snapshot!(content, @r"
+    name: Setup
+    runs:
+      using: composite
+      steps:
+        - uses: actions/checkout@b4ffde65f46336ab88eb53be808477a3936bae11 # v4
+    ");
+
+    // Pinning again changes nothing
+    action_format_snapshot!(context.filters(), context.command().args(["pin", "--check"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_pin_check() {
+    let context = TestContext::new();
+    context.file(".github/actions.lock", LOCK);
+    let workflow = r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/checkout@v3
+      - uses: docker/login-action@v3
+";
+    context.workflow("ci.yml", workflow);
+
+    action_format_snapshot!(context.filters(), context.command().args(["pin", "--check"]), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/workflows/ci.yml:8:15: `actions/checkout@v3` is missing from .github/actions.lock
+    .github/workflows/ci.yml:9:15: `docker/login-action@v3` is missing from .github/actions.lock
+    Would pin: .github/workflows/ci.yml
+
+    ----- stderr -----
+    ");
+
+    // Original file left unchanged
+    assert_eq!(context.read_workflow("ci.yml"), workflow);
+}
+
+#[test]
+fn test_pin_missing_entries_still_pins_locked_actions() {
+    let context = TestContext::new();
+    context.file(".github/actions.lock", LOCK);
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: docker/login-action@v3
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("pin"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .github/workflows/ci.yml:8:15: `docker/login-action@v3` is missing from .github/actions.lock
+    Pinned: .github/workflows/ci.yml
+
+    ----- stderr -----
+    ");
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    name: CI
+    on: push
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        steps:
+          - uses: actions/checkout@b4ffde65f46336ab88eb53be808477a3936bae11 # v4
+          - uses: docker/login-action@v3
+    ");
+}
+
+#[test]
+fn test_pin_keeps_existing_comment() {
+    let context = TestContext::new();
+    context.file(
+        ".github/actions.lock",
+        "actions/checkout@v3 f43a0e5ff2bd294095638e18286ca9a3d1956744\n",
+    );
+    context.config(
+        r#"
+[lint]
+select = ["AF002", "AF010"]
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v3 # keep me
+",
+    );
+
+    context.command().arg("pin").assert().success();
+
+    let content = context.read_workflow("ci.yml");
+    insta::assert_snapshot!(content, @r"
+    name: CI
+    on: push
+    jobs:
+      build:
+        runs-on: ubuntu-latest
+        steps:
+          - uses: actions/checkout@f43a0e5ff2bd294095638e18286ca9a3d1956744 # v3 keep me
+    ");
+
+    // The version is still read from the comment
+    action_format_snapshot!(context.filters(), context.command().arg("lint"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .github/workflows/ci.yml:7:15: warning[AF010] `actions/checkout@v3` is deprecated, use `actions/checkout@v4` instead
+    Found 1 problem
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_pin_trusted_actions_skipped() {
+    let context = TestContext::new();
+    context.file(".github/actions.lock", "");
+    context.config(
+        r#"
+[lint.unpinned_action]
+trusted = ["actions/*"]
+"#,
+    );
+    context.workflow(
+        "ci.yml",
+        r"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().args(["pin", "--check"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_pin_no_lock_file() {
+    let context = TestContext::new();
+
+    action_format_snapshot!(context.filters(), context.command().arg("pin"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No .github/actions.lock file found
+    ");
+}
+
+#[test]
+fn test_pin_invalid_lock_file() {
+    let context = TestContext::new();
+    context.file(
+        ".github/actions.lock",
+        r"actions/checkout@v4 b4ffde65f46336ab88eb53be808477a3936bae11
+actions/cache@v4 v4.2.0
+",
+    );
+
+    action_format_snapshot!(context.filters(), context.command().arg("pin"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid lock file '.github/actions.lock' at line 2: `v4.2.0` is not a full commit SHA
+    ");
+}